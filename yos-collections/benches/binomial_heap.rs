@@ -0,0 +1,52 @@
+//! Compares the arena backed `BinomialHeap` with the boxed node functions.
+//!
+//! Run it with `cargo bench -p yos-collections --bench binomial_heap`.
+mod common;
+
+use common::{measure, values};
+use std::hint::black_box;
+use yos_collections::binomial_heap::{self, BinomialHeap, Node};
+
+const SIZES: [usize; 3] = [1_000, 10_000, 100_000];
+
+fn arena_push_pop(xs: &[u64]) {
+    let mut h = BinomialHeap::with_capacity(xs.len());
+    for x in xs {
+        h.push(*x);
+    }
+    while let Some(x) = h.pop() {
+        black_box(x);
+    }
+}
+
+fn boxed_push_pop(xs: &[u64]) {
+    let mut root: Option<Box<Node<u64>>> = None;
+    for x in xs {
+        binomial_heap::push(&mut root, *x);
+    }
+    while let Some(x) = binomial_heap::pop(&mut root) {
+        black_box(x);
+    }
+}
+
+fn main() {
+    println!(
+        "{:>10} {:>14} {:>14} {:>8}",
+        "size", "arena", "boxed", "ratio"
+    );
+
+    for size in SIZES.iter() {
+        let xs = values(*size);
+
+        let arena = measure(|| arena_push_pop(black_box(&xs)));
+        let boxed = measure(|| boxed_push_pop(black_box(&xs)));
+
+        println!(
+            "{:>10} {:>14?} {:>14?} {:>8.2}",
+            size,
+            arena,
+            boxed,
+            boxed.as_secs_f64() / arena.as_secs_f64()
+        );
+    }
+}
@@ -0,0 +1,29 @@
+//! Helpers shared by the benchmarks.
+use std::time::{Duration, Instant};
+
+const ROUNDS: u32 = 3;
+
+/// A tiny linear congruential generator, good enough to shuffle the input.
+pub fn values(len: usize) -> Vec<u64> {
+    let mut seed = 42u64;
+    (0..len)
+        .map(|_| {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            seed >> 33
+        })
+        .collect()
+}
+
+/// Runs the closure a few times and returns the best time.
+pub fn measure<F: FnMut()>(mut f: F) -> Duration {
+    (0..ROUNDS)
+        .map(|_| {
+            let start = Instant::now();
+            f();
+            start.elapsed()
+        })
+        .min()
+        .unwrap()
+}
@@ -0,0 +1,52 @@
+//! Compares the `DaryHeap` for several arities with the `BinaryHeap`.
+//!
+//! Run it with `cargo bench -p yos-collections --bench dary_heap`.
+mod common;
+
+use common::{measure, values};
+use std::hint::black_box;
+use yos_collections::binary_heap::BinaryHeap;
+use yos_collections::dary_heap::DaryHeap;
+
+const SIZES: [usize; 3] = [10_000, 100_000, 1_000_000];
+
+fn binary_push_pop(xs: &[u64]) {
+    let mut h = BinaryHeap::with_capacity(xs.len());
+    for x in xs {
+        h.push(*x);
+    }
+    while let Some(x) = h.pop() {
+        black_box(x);
+    }
+}
+
+fn dary_push_pop<const D: usize>(xs: &[u64]) {
+    let mut h = DaryHeap::<u64, D>::with_capacity(xs.len());
+    for x in xs {
+        h.push(*x);
+    }
+    while let Some(x) = h.pop() {
+        black_box(x);
+    }
+}
+
+fn main() {
+    println!(
+        "{:>10} {:>14} {:>14} {:>14} {:>14}",
+        "size", "binary", "d=2", "d=4", "d=8"
+    );
+
+    for size in SIZES.iter() {
+        let xs = values(*size);
+
+        let binary = measure(|| binary_push_pop(black_box(&xs)));
+        let d2 = measure(|| dary_push_pop::<2>(black_box(&xs)));
+        let d4 = measure(|| dary_push_pop::<4>(black_box(&xs)));
+        let d8 = measure(|| dary_push_pop::<8>(black_box(&xs)));
+
+        println!(
+            "{:>10} {:>14?} {:>14?} {:>14?} {:>14?}",
+            size, binary, d2, d4, d8
+        );
+    }
+}
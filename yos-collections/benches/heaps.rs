@@ -0,0 +1,48 @@
+//! Compares the heaps behind the `PriorityQueue` trait on a push and pop workload.
+//!
+//! Run it with `cargo bench -p yos-collections --bench heaps`.
+mod common;
+
+use common::{measure, values};
+use std::hint::black_box;
+use yos_collections::binary_heap::BinaryHeap;
+use yos_collections::binomial_heap::BinomialHeap;
+use yos_collections::pairing_heap::PairingHeap;
+use yos_collections::priority_queue::PriorityQueue;
+
+const SIZES: [usize; 3] = [1_000, 10_000, 100_000];
+
+/// Pushes all the values, then pops half of them while pushing
+/// them back, and finally pops everything.
+fn push_pop<Q: PriorityQueue<u64>>(mut queue: Q, xs: &[u64]) {
+    for x in xs {
+        queue.push(*x);
+    }
+    for x in xs.iter().take(xs.len() / 2) {
+        black_box(queue.pop());
+        queue.push(*x);
+    }
+    while let Some(x) = queue.pop() {
+        black_box(x);
+    }
+}
+
+fn main() {
+    println!(
+        "{:>10} {:>14} {:>14} {:>14}",
+        "size", "binary", "binomial", "pairing"
+    );
+
+    for size in SIZES.iter() {
+        let xs = values(*size);
+
+        let binary = measure(|| push_pop(BinaryHeap::new(), black_box(&xs)));
+        let binomial = measure(|| push_pop(BinomialHeap::new(), black_box(&xs)));
+        let pairing = measure(|| push_pop(PairingHeap::new(), black_box(&xs)));
+
+        println!(
+            "{:>10} {:>14?} {:>14?} {:>14?}",
+            size, binary, binomial, pairing
+        );
+    }
+}
@@ -0,0 +1,61 @@
+//! Compares the `MultiQueue` with a `BinaryHeap` behind a single `Mutex`,
+//! with every thread pushing and popping at the same time on a queue which
+//! keeps a backlog of elements, like the queue of a busy scheduler.
+//!
+//! Run it with `cargo bench -p yos-collections --bench multi_queue`.
+mod common;
+
+use common::{measure, values};
+use std::hint::black_box;
+use std::sync::Mutex;
+use std::thread;
+use yos_collections::binary_heap::BinaryHeap;
+use yos_collections::multi_queue::MultiQueue;
+
+const THREADS: [usize; 3] = [1, 4, 8];
+const OPERATIONS: usize = 200_000;
+const BACKLOG: usize = 10_000;
+
+/// Every thread pushes its share of the values, popping after every push.
+fn mutex_heap(xs: &[u64], threads: usize) {
+    let heap = Mutex::new(BinaryHeap::from(xs[..BACKLOG].to_vec()));
+    thread::scope(|s| {
+        for chunk in xs.chunks(xs.len() / threads) {
+            let heap = &heap;
+            s.spawn(move || {
+                for x in chunk {
+                    heap.lock().unwrap().push(*x);
+                    black_box(heap.lock().unwrap().pop());
+                }
+            });
+        }
+    });
+}
+
+fn multi_queue(xs: &[u64], threads: usize) {
+    let mut queue = MultiQueue::new(2 * threads);
+    queue.extend(xs[..BACKLOG].iter().copied());
+    thread::scope(|s| {
+        for chunk in xs.chunks(xs.len() / threads) {
+            let queue = &queue;
+            s.spawn(move || {
+                for x in chunk {
+                    queue.push(*x);
+                    black_box(queue.pop());
+                }
+            });
+        }
+    });
+}
+
+fn main() {
+    println!("{:>10} {:>14} {:>14}", "threads", "mutex", "multiqueue");
+
+    let xs = values(OPERATIONS);
+    for threads in THREADS.iter() {
+        let mutex = measure(|| mutex_heap(black_box(&xs), *threads));
+        let multi = measure(|| multi_queue(black_box(&xs), *threads));
+
+        println!("{:>10} {:>14?} {:>14?}", threads, mutex, multi);
+    }
+}
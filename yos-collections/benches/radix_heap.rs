@@ -0,0 +1,56 @@
+//! Compares the `RadixHeap` with the `BinaryHeap` on a monotone workload,
+//! where every pushed key is at least the last popped key.
+//!
+//! Run it with `cargo bench -p yos-collections --bench radix_heap`.
+mod common;
+
+use common::{measure, values};
+use std::hint::black_box;
+use yos_collections::binary_heap::BinaryHeap;
+use yos_collections::radix_heap::RadixHeap;
+
+const SIZES: [usize; 3] = [10_000, 100_000, 1_000_000];
+
+/// Pushes two keys for every pop, each key is the last popped key plus
+/// a weight, like the relaxed edges of Dijkstra's algorithm.
+fn binary_monotone(ws: &[u64]) {
+    let mut h = BinaryHeap::new_min();
+    h.push(0u64);
+    for pair in ws.chunks(2) {
+        let last = h.pop().unwrap_or(0);
+        for w in pair {
+            h.push(last + w);
+        }
+    }
+    while let Some(x) = h.pop() {
+        black_box(x);
+    }
+}
+
+fn radix_monotone(ws: &[u64]) {
+    let mut h = RadixHeap::new();
+    h.push(0u64, ());
+    for pair in ws.chunks(2) {
+        let last = h.pop().map(|(k, _)| k).unwrap_or(0);
+        for w in pair {
+            h.push(last + w, ());
+        }
+    }
+    while let Some(x) = h.pop() {
+        black_box(x);
+    }
+}
+
+fn main() {
+    println!("{:>10} {:>14} {:>14}", "size", "binary", "radix");
+
+    for size in SIZES.iter() {
+        // Small edge weights, so that the keys stay close to the last popped key.
+        let ws: Vec<u64> = values(*size).iter().map(|w| w % 1000).collect();
+
+        let binary = measure(|| binary_monotone(black_box(&ws)));
+        let radix = measure(|| radix_monotone(black_box(&ws)));
+
+        println!("{:>10} {:>14?} {:>14?}", size, binary, radix);
+    }
+}
@@ -14,14 +14,25 @@ fn main() {
 
     append(&mut bxa, Some(bxb));
 
-    assert_eq!(bxa.item, 20);
-    assert_eq!(bxa.order, 2);
+    assert_eq!(*bxa.item(), 20);
+    assert_eq!(bxa.order(), 2);
 
-    let bxx = bxa.child.as_ref().unwrap();
-    assert_eq!(bxx.item, 19);
-    assert_eq!(bxx.order, 0);
+    let bxx = bxa.child().unwrap();
+    assert_eq!(*bxx.item(), 19);
+    assert_eq!(bxx.order(), 0);
 
-    let bxy = bxx.sibling.as_ref().unwrap();
-    assert_eq!(bxy.item, 10);
-    assert_eq!(bxy.order, 1);
+    let bxy = bxx.sibling().unwrap();
+    assert_eq!(*bxy.item(), 10);
+    assert_eq!(bxy.order(), 1);
+
+    let root = Some(bxa);
+    assert!(validate(&root).is_ok());
+
+    let mut h: BinomialHeap<i32> = vec![3, 1, 4, 1, 5].into_iter().collect();
+    h.push(9);
+    println!("heap={:?}", h);
+
+    while let Some(x) = h.pop() {
+        println!("pop={}", x);
+    }
 }
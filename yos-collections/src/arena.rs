@@ -0,0 +1,481 @@
+//! A vector backed storage where the values are addressed by their index.
+use alloc::vec::Vec;
+use core::iter::Enumerate;
+use core::mem;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+/// The identity of the next created arena.
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// A stable reference to an element pushed into a heap.
+///
+/// The handle stays valid while the element is in the heap, no matter how
+/// the element moves inside the heap. Once the element is popped or removed
+/// the handle is stale: the heap returns `None` for it, or panics where the
+/// element has to be in the heap, even after a later push reuses its slot.
+/// A handle is valid only in the heap which returned it, not in its clones.
+/// After a meld the handles of both heaps name their elements in the
+/// melded heap.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Handle {
+    index: usize,
+    generation: u64,
+    arena: usize,
+}
+
+impl Handle {
+    /// Returns the identity of the arena which stores the element.
+    pub(crate) fn arena(&self) -> usize {
+        self.arena
+    }
+}
+
+/// An entry of the arena. A vacant entry links to the next vacant entry,
+/// so the vacant entries form a free list threaded through the vector.
+#[derive(Clone)]
+enum Entry<T> {
+    /// A stored value, together with the generation of its insert.
+    Occupied {
+        generation: u64,
+        value: T,
+    },
+    Vacant(Option<usize>),
+}
+
+/// A vector backed storage with a free list. The index of a value stays
+/// the same for as long as the value is stored, and the indexes of the
+/// removed values are reused by the later inserts.
+///
+/// Every insert is tagged with a new generation, so a handle names a single
+/// insert into a single arena and is never confused with a later value
+/// stored at the same index.
+pub(crate) struct Arena<T> {
+    entries: Vec<Entry<T>>,
+    /// The first vacant entry.
+    free: Option<usize>,
+    len: usize,
+    /// The identity of the arena, which is not shared with any other arena.
+    id: usize,
+    /// The generation of the next insert.
+    generation: u64,
+}
+
+impl<T: Clone> Clone for Arena<T> {
+    fn clone(&self) -> Self {
+        // The clone gets its own identity, the handles of the original
+        // arena don't name its values.
+        Self {
+            entries: self.entries.clone(),
+            free: self.free,
+            len: self.len,
+            id: next_id(),
+            generation: self.generation,
+        }
+    }
+}
+
+impl<T> Default for Arena<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Arena<T> {
+    /// Creates a new empty arena.
+    pub(crate) fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    /// Creates a new empty arena which can hold `capacity` values
+    /// without reallocating.
+    pub(crate) fn with_capacity(capacity: usize) -> Self {
+        Self {
+            entries: Vec::with_capacity(capacity),
+            free: None,
+            len: 0,
+            id: next_id(),
+            generation: 0,
+        }
+    }
+
+    /// Returns the identity of the arena.
+    pub(crate) fn id(&self) -> usize {
+        self.id
+    }
+
+    /// Returns the number of values the arena can hold without reallocating.
+    pub(crate) fn capacity(&self) -> usize {
+        self.entries.capacity()
+    }
+
+    /// Reserves capacity for at least `additional` more values.
+    pub(crate) fn reserve(&mut self, additional: usize) {
+        let vacant = self.entries.len() - self.len;
+        if additional > vacant {
+            self.entries.reserve(additional - vacant);
+        }
+    }
+
+    /// Reserves the minimum capacity for exactly `additional` more values.
+    pub(crate) fn reserve_exact(&mut self, additional: usize) {
+        let vacant = self.entries.len() - self.len;
+        if additional > vacant {
+            self.entries.reserve_exact(additional - vacant);
+        }
+    }
+
+    /// Shrinks the allocated memory. The vacant entries at the end of the
+    /// vector are dropped, the indexes of the stored values don't change.
+    pub(crate) fn shrink_to_fit(&mut self) {
+        while let Some(Entry::Vacant(_)) = self.entries.last() {
+            self.entries.pop();
+        }
+
+        // Rebuild the free list from the remaining vacant entries.
+        self.free = None;
+        for (i, entry) in self.entries.iter_mut().enumerate().rev() {
+            if let Entry::Vacant(next) = entry {
+                *next = self.free;
+                self.free = Some(i);
+            }
+        }
+
+        self.entries.shrink_to_fit();
+    }
+
+    /// Returns the number of stored values.
+    pub(crate) fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns the index which the next insert returns.
+    pub(crate) fn vacant_index(&self) -> usize {
+        self.free.unwrap_or(self.entries.len())
+    }
+
+    /// Stores a value and returns its index.
+    pub(crate) fn insert(&mut self, value: T) -> usize {
+        let generation = self.generation;
+        self.generation += 1;
+        self.len += 1;
+
+        let occupied = Entry::Occupied { generation, value };
+        match self.free {
+            Some(i) => {
+                let entry = mem::replace(&mut self.entries[i], occupied);
+                match entry {
+                    Entry::Vacant(next) => self.free = next,
+                    Entry::Occupied { .. } => {
+                        unreachable!("the free list links an occupied entry")
+                    }
+                }
+                i
+            }
+            None => {
+                self.entries.push(occupied);
+                self.entries.len() - 1
+            }
+        }
+    }
+
+    /// Removes the value at `index` and returns it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no value at `index`.
+    pub(crate) fn remove(&mut self, index: usize) -> T {
+        let entry = mem::replace(&mut self.entries[index], Entry::Vacant(self.free));
+        match entry {
+            Entry::Occupied { value, .. } => {
+                self.free = Some(index);
+                self.len -= 1;
+                value
+            }
+            Entry::Vacant(_) => panic!("the entry {} is vacant", index),
+        }
+    }
+
+    /// Returns the value at `index`, or `None` if the entry is vacant.
+    pub(crate) fn get(&self, index: usize) -> Option<&T> {
+        match self.entries.get(index) {
+            Some(Entry::Occupied { value, .. }) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the value at `index`, or `None` if the entry is vacant.
+    pub(crate) fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        match self.entries.get_mut(index) {
+            Some(Entry::Occupied { value, .. }) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the handle of the value at `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no value at `index`.
+    pub(crate) fn handle(&self, index: usize) -> Handle {
+        match self.entries.get(index) {
+            Some(Entry::Occupied { generation, .. }) => Handle {
+                index,
+                generation: *generation,
+                arena: self.id,
+            },
+            _ => panic!("the entry {} is vacant", index),
+        }
+    }
+
+    /// Returns the index of the value named by the handle, or `None` if the
+    /// value was removed or the handle was returned by another arena.
+    pub(crate) fn index(&self, handle: Handle) -> Option<usize> {
+        if handle.arena != self.id {
+            return None;
+        }
+
+        match self.entries.get(handle.index) {
+            Some(Entry::Occupied { generation, .. }) if *generation == handle.generation => {
+                Some(handle.index)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the values at two different indexes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the indexes are equal or if any of the entries is vacant.
+    pub(crate) fn get2_mut(&mut self, a: usize, b: usize) -> (&mut T, &mut T) {
+        assert!(a != b);
+
+        let (x, y) = if a < b {
+            let (left, right) = self.entries.split_at_mut(b);
+            (&mut left[a], &mut right[0])
+        } else {
+            let (left, right) = self.entries.split_at_mut(a);
+            (&mut right[0], &mut left[b])
+        };
+
+        match (x, y) {
+            (Entry::Occupied { value: x, .. }, Entry::Occupied { value: y, .. }) => (x, y),
+            _ => panic!("the entry {} or {} is vacant", a, b),
+        }
+    }
+
+    /// Returns an iterator over the mutable stored values.
+    pub(crate) fn values_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.entries.iter_mut().filter_map(|entry| match entry {
+            Entry::Occupied { value, .. } => Some(value),
+            Entry::Vacant(_) => None,
+        })
+    }
+
+    /// Removes all the values. The generations keep counting, so the
+    /// handles of the removed values stay stale.
+    pub(crate) fn clear(&mut self) {
+        self.entries.clear();
+        self.free = None;
+        self.len = 0;
+    }
+
+    /// Returns an iterator over the indexes and the stored values.
+    pub(crate) fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            iter: self.entries.iter().enumerate(),
+        }
+    }
+}
+
+/// Returns a new identity for an arena.
+fn next_id() -> usize {
+    NEXT_ID.fetch_add(1, Ordering::Relaxed)
+}
+
+impl<T> IntoIterator for Arena<T> {
+    type Item = (usize, T);
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter {
+            iter: self.entries.into_iter(),
+            index: 0,
+        }
+    }
+}
+
+/// An iterator over the indexes and the values stored in an arena.
+pub(crate) struct Iter<'a, T> {
+    iter: Enumerate<core::slice::Iter<'a, Entry<T>>>,
+}
+
+impl<T> Clone for Iter<'_, T> {
+    fn clone(&self) -> Self {
+        Iter {
+            iter: self.iter.clone(),
+        }
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = (usize, &'a T);
+
+    fn next(&mut self) -> Option<(usize, &'a T)> {
+        self.iter.by_ref().find_map(|(i, entry)| match entry {
+            Entry::Occupied { value, .. } => Some((i, value)),
+            Entry::Vacant(_) => None,
+        })
+    }
+}
+
+/// An owning iterator over the indexes and the values stored in an arena.
+pub(crate) struct IntoIter<T> {
+    iter: alloc::vec::IntoIter<Entry<T>>,
+    /// The index of the next entry.
+    index: usize,
+}
+
+impl<T> IntoIter<T> {
+    /// Returns an iterator over the values which were not consumed yet.
+    pub(crate) fn remaining(&self) -> impl Iterator<Item = &T> {
+        self.iter.as_slice().iter().filter_map(|entry| match entry {
+            Entry::Occupied { value, .. } => Some(value),
+            Entry::Vacant(_) => None,
+        })
+    }
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = (usize, T);
+
+    fn next(&mut self) -> Option<(usize, T)> {
+        for entry in self.iter.by_ref() {
+            let i = self.index;
+            self.index += 1;
+
+            if let Entry::Occupied { value, .. } = entry {
+                return Some((i, value));
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insert_remove_pass() {
+        let mut a = Arena::new();
+        let x = a.insert(10);
+        let y = a.insert(20);
+        assert_eq!(2, a.len());
+        assert_eq!(Some(&10), a.get(x));
+
+        assert_eq!(10, a.remove(x));
+        assert_eq!(None, a.get(x));
+        assert_eq!(Some(&20), a.get(y));
+
+        // The vacant entry is reused.
+        let z = a.insert(30);
+        assert_eq!(x, z);
+        assert_eq!(2, a.len());
+    }
+
+    #[test]
+    fn handle_pass() {
+        let mut a = Arena::new();
+        let x = a.insert(10);
+        let hx = a.handle(x);
+        assert_eq!(Some(x), a.index(hx));
+
+        // The reused entry gets a new generation.
+        a.remove(x);
+        let y = a.insert(20);
+        assert_eq!(x, y);
+        assert_eq!(None, a.index(hx));
+        assert_eq!(Some(y), a.index(a.handle(y)));
+
+        // The handles of another arena, or of a clone, are not valid.
+        let b = a.clone();
+        assert_eq!(None, b.index(a.handle(y)));
+
+        let hy = a.handle(y);
+        a.clear();
+        a.insert(30);
+        assert_eq!(None, a.index(hy));
+    }
+
+    #[test]
+    #[should_panic]
+    fn remove_vacant_fail() {
+        let mut a = Arena::new();
+        let x = a.insert(10);
+        a.remove(x);
+        a.remove(x);
+    }
+
+    #[test]
+    fn capacity_pass() {
+        let mut a = Arena::with_capacity(10);
+        assert!(a.capacity() >= 10);
+
+        let xs: Vec<usize> = (0..10).map(|x| a.insert(x)).collect();
+        let capacity = a.capacity();
+
+        xs.iter().step_by(2).for_each(|x| {
+            a.remove(*x);
+        });
+        (0..5).for_each(|x| {
+            a.insert(x);
+        });
+        assert_eq!(capacity, a.capacity());
+
+        a.reserve(5);
+        assert!(a.capacity() >= 15);
+    }
+
+    #[test]
+    fn shrink_to_fit_pass() {
+        let mut a = Arena::new();
+        let xs: Vec<usize> = (0..10).map(|x| a.insert(x)).collect();
+        a.remove(xs[2]);
+        a.remove(xs[8]);
+        a.remove(xs[9]);
+
+        a.shrink_to_fit();
+        assert_eq!(7, a.len());
+        assert_eq!(Some(&7), a.get(xs[7]));
+
+        // The only vacant entry left is reused.
+        assert_eq!(xs[2], a.insert(2));
+        assert_eq!(8, a.insert(8));
+    }
+
+    #[test]
+    fn get2_mut_pass() {
+        let mut a = Arena::new();
+        let x = a.insert(1);
+        let y = a.insert(2);
+
+        let (vx, vy) = a.get2_mut(y, x);
+        mem::swap(vx, vy);
+        assert_eq!(Some(&2), a.get(x));
+        assert_eq!(Some(&1), a.get(y));
+    }
+
+    #[test]
+    fn iter_pass() {
+        let mut a = Arena::new();
+        let xs: Vec<usize> = (0..5).map(|x| a.insert(x * 10)).collect();
+        a.remove(xs[1]);
+
+        let ys: Vec<(usize, &usize)> = a.iter().collect();
+        assert_eq!(vec![(0, &0), (2, &20), (3, &30), (4, &40)], ys);
+
+        let ys: Vec<(usize, usize)> = a.into_iter().collect();
+        assert_eq!(vec![(0, 0), (2, 20), (3, 30), (4, 40)], ys);
+    }
+}
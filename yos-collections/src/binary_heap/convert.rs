@@ -1,19 +1,26 @@
 use super::BinaryHeap;
+use crate::compare::{Compare, MaxComparator};
+use alloc::vec::Vec;
 use core::iter::FromIterator;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 impl<T: Ord> From<Vec<T>> for BinaryHeap<T> {
     /// Converts a `Vec<T>` into a `BinaryHeap<T>`.
     ///
     /// This conversion happens in-place, and has `O(n)` time complexity.
     fn from(vec: Vec<T>) -> Self {
-        let mut heap = Self { data: vec };
+        let mut heap = Self {
+            data: vec,
+            cmp: MaxComparator,
+        };
         heap.rebuild();
         heap
     }
 }
 
-impl<T> From<BinaryHeap<T>> for Vec<T> {
-    fn from(heap: BinaryHeap<T>) -> Vec<T> {
+impl<T, C> From<BinaryHeap<T, C>> for Vec<T> {
+    fn from(heap: BinaryHeap<T, C>) -> Vec<T> {
         heap.data
     }
 }
@@ -24,6 +31,47 @@ impl<T: Ord> FromIterator<T> for BinaryHeap<T> {
     }
 }
 
+impl<T, C: Compare<T>> Extend<T> for BinaryHeap<T, C> {
+    /// Extends the binary heap with the elements of an iterator. When many
+    /// elements arrive at once the heap is rebuilt instead of pushing them
+    /// one by one.
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let start = self.data.len();
+        self.data.extend(iter);
+        self.rebuild_tail(start);
+    }
+}
+
+impl<'a, T: 'a + Copy, C: Compare<T>> Extend<&'a T> for BinaryHeap<T, C> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().cloned());
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: Serialize, C> Serialize for BinaryHeap<T, C> {
+    /// Serializes the elements as a sequence, in the order they are stored.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.data.iter())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T, C> Deserialize<'de> for BinaryHeap<T, C>
+where
+    T: Deserialize<'de>,
+    C: Compare<T> + Default,
+{
+    /// Deserializes a sequence of elements. The order of the sequence is not
+    /// trusted, the heap is rebuilt in O(n) time.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut heap = Self::from_comparator(C::default());
+        heap.data = Vec::deserialize(deserializer)?;
+        heap.rebuild();
+        Ok(heap)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -41,11 +89,70 @@ mod tests {
     #[test]
     fn from_iterator() {
         let xs = vec![3, 1, 4];
-        let mut h = BinaryHeap::from_iter(xs.into_iter());
+        let mut h = BinaryHeap::from_iter(xs);
 
         h.push(2);
 
         let xs: Vec<i32> = h.into();
         assert_eq!(xs.len(), 4);
     }
+
+    #[test]
+    fn extend_pass() {
+        let mut h = BinaryHeap::from((0..100).collect::<Vec<_>>());
+        h.extend(vec![200, -5]);
+        assert_eq!(Ok(()), h.validate());
+        assert_eq!(Some(&200), h.peek());
+
+        h.extend(1000..2000);
+        assert_eq!(Ok(()), h.validate());
+        assert_eq!(1102, h.len());
+        assert_eq!(Some(&1999), h.peek());
+    }
+
+    #[test]
+    fn extend_ref_pass() {
+        let mut h = BinaryHeap::new_min();
+        h.extend(&[3, 1, 2]);
+        assert_eq!(vec![3, 2, 1], h.into_sorted_vec());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serialize_pass() {
+        use serde_test::{assert_ser_tokens, Token};
+
+        let h = BinaryHeap::from(vec![1, 3, 2]);
+        assert_ser_tokens(
+            &h,
+            &[
+                Token::Seq { len: Some(3) },
+                Token::I32(3),
+                Token::I32(1),
+                Token::I32(2),
+                Token::SeqEnd,
+            ],
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize_pass() {
+        use crate::compare::MinComparator;
+        use serde::de::value::{Error, SeqDeserializer};
+        use serde::de::IntoDeserializer;
+
+        // The input is not a heap, it has to be rebuilt.
+        let xs: Vec<i32> = (0..100).collect();
+        let de: SeqDeserializer<_, Error> = xs.into_deserializer();
+        let h = BinaryHeap::<i32>::deserialize(de).unwrap();
+        assert_eq!(Ok(()), h.validate());
+        assert_eq!(Some(&99), h.peek());
+
+        let xs: Vec<i32> = (0..100).rev().collect();
+        let de: SeqDeserializer<_, Error> = xs.into_deserializer();
+        let h = BinaryHeap::<i32, MinComparator>::deserialize(de).unwrap();
+        assert_eq!(Ok(()), h.validate());
+        assert_eq!((0..100).rev().collect::<Vec<_>>(), h.into_sorted_vec());
+    }
 }
@@ -1,8 +1,11 @@
 /// An iterator over the elements in a binary heap.
 use super::BinaryHeap;
+use crate::compare::Compare;
 
-use std::fmt::Debug;
-use std::slice;
+use alloc::vec;
+use core::fmt::Debug;
+use core::iter::FusedIterator;
+use core::slice;
 
 /// Iterator
 pub struct Iter<'a, T: 'a> {
@@ -17,7 +20,7 @@ impl<'a, T> Iter<'a, T> {
 }
 
 impl<T: Debug> Debug for Iter<'_, T> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.debug_tuple("Iter").field(&self.iter.as_slice()).finish()
     }
 }
@@ -59,18 +62,18 @@ impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
 /// An owning iterator over the elements of a `BinaryHeap`.
 #[derive(Clone)]
 pub struct IntoIter<T> {
-    iter: std::vec::IntoIter<T>,
+    iter: vec::IntoIter<T>,
 }
 
 impl<T> IntoIter<T> {
     /// Creates a new instance of the into iterator.
-    pub fn new(iter: std::vec::IntoIter<T>) -> Self {
+    pub fn new(iter: vec::IntoIter<T>) -> Self {
         Self { iter }
     }
 }
 
 impl<T: Debug> Debug for IntoIter<T> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.debug_tuple("IntoIter")
             .field(&self.iter.as_slice())
             .finish()
@@ -98,7 +101,91 @@ impl<T> DoubleEndedIterator for IntoIter<T> {
     }
 }
 
-impl<T> IntoIterator for BinaryHeap<T> {
+/// A draining iterator over the elements of a `BinaryHeap`.
+pub struct Drain<'a, T: 'a> {
+    iter: vec::Drain<'a, T>,
+}
+
+impl<'a, T> Drain<'a, T> {
+    /// Creates a new instance of the draining iterator.
+    pub fn new(iter: vec::Drain<'a, T>) -> Self {
+        Self { iter }
+    }
+}
+
+impl<T: Debug> Debug for Drain<'_, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("Drain").field(&self.iter.as_slice()).finish()
+    }
+}
+
+impl<T> Iterator for Drain<'_, T> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        self.iter.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for Drain<'_, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<T> {
+        self.iter.next_back()
+    }
+}
+
+impl<T> ExactSizeIterator for Drain<'_, T> {}
+
+impl<T> FusedIterator for Drain<'_, T> {}
+
+/// An owning iterator over the elements of a `BinaryHeap`,
+/// which yields the elements in heap order.
+#[derive(Clone)]
+pub struct IntoIterSorted<T, C> {
+    heap: BinaryHeap<T, C>,
+}
+
+impl<T, C> IntoIterSorted<T, C> {
+    /// Creates a new instance of the sorted into iterator.
+    pub fn new(heap: BinaryHeap<T, C>) -> Self {
+        Self { heap }
+    }
+}
+
+impl<T: Debug, C> Debug for IntoIterSorted<T, C> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("IntoIterSorted")
+            .field(&self.heap.as_slice())
+            .finish()
+    }
+}
+
+impl<T, C: Compare<T>> Iterator for IntoIterSorted<T, C> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        self.heap.pop()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.heap.len();
+        (len, Some(len))
+    }
+}
+
+impl<T, C: Compare<T>> ExactSizeIterator for IntoIterSorted<T, C> {}
+
+impl<T, C: Compare<T>> FusedIterator for IntoIterSorted<T, C> {}
+
+impl<T, C> IntoIterator for BinaryHeap<T, C> {
     type Item = T;
     type IntoIter = IntoIter<T>;
 
@@ -107,7 +194,7 @@ impl<T> IntoIterator for BinaryHeap<T> {
     }
 }
 
-impl<'a, T> IntoIterator for &'a BinaryHeap<T> {
+impl<'a, T, C> IntoIterator for &'a BinaryHeap<T, C> {
     type Item = &'a T;
     type IntoIter = Iter<'a, T>;
 
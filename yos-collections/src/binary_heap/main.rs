@@ -1,35 +1,48 @@
 //! Implementation of the binary heap
 use super::iter::*;
+use super::peek_mut::PeekMut;
+use crate::compare::{Compare, FnComparator, KeyComparator, MaxComparator, MinComparator};
+use crate::dot::Dot;
+use crate::invariant::InvariantError;
 use yos_memcursor::MemCursor;
 
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt::Debug;
 use core::mem::swap;
-use std::fmt::Debug;
 
 /// Implementation of the binary heap.
-pub struct BinaryHeap<T> {
+///
+/// The order of the elements is given by the comparator `C`. By default the
+/// heap is a max-heap, which keeps the greatest element at the top.
+pub struct BinaryHeap<T, C = MaxComparator> {
     pub(crate) data: Vec<T>,
+    pub(crate) cmp: C,
 }
 
-impl<T: Clone> Clone for BinaryHeap<T> {
+impl<T: Clone, C: Clone> Clone for BinaryHeap<T, C> {
     fn clone(&self) -> Self {
         Self {
             data: self.data.clone(),
+            cmp: self.cmp.clone(),
         }
     }
 
     fn clone_from(&mut self, source: &Self) {
         self.data.clone_from(&source.data);
+        self.cmp.clone_from(&source.cmp);
     }
 }
 
-impl<T: Ord> Default for BinaryHeap<T> {
+impl<T, C: Compare<T> + Default> Default for BinaryHeap<T, C> {
     fn default() -> Self {
-        Self::new()
+        Self::from_comparator(C::default())
     }
 }
 
-impl<T: Debug> Debug for BinaryHeap<T> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl<T: Debug, C> Debug for BinaryHeap<T, C> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.debug_list().entries(self.iter()).finish()
     }
 }
@@ -50,9 +63,7 @@ impl<T: Ord> BinaryHeap<T> {
     /// assert_eq!(3, heap.len());
     /// ```
     pub fn new() -> Self {
-        Self {
-            data: Vec::<T>::new(),
-        }
+        Self::from_comparator(MaxComparator)
     }
 
     /// Creates a new instance of the binary heap with a given capacity.
@@ -72,6 +83,108 @@ impl<T: Ord> BinaryHeap<T> {
     pub fn with_capacity(capacity: usize) -> Self {
         Self {
             data: Vec::<T>::with_capacity(capacity),
+            cmp: MaxComparator,
+        }
+    }
+}
+
+impl<T: Ord> BinaryHeap<T, MinComparator> {
+    /// Creates a new instance of a min-heap, which keeps
+    /// the smallest element at the top.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::binary_heap::BinaryHeap;
+    ///
+    /// let mut heap = BinaryHeap::new_min();
+    /// heap.push(3);
+    /// heap.push(1);
+    /// heap.push(2);
+    ///
+    /// assert_eq!(Some(1), heap.pop());
+    /// ```
+    pub fn new_min() -> Self {
+        Self::from_comparator(MinComparator)
+    }
+}
+
+impl<T, F> BinaryHeap<T, FnComparator<F>>
+where
+    F: Fn(&T, &T) -> Ordering,
+{
+    /// Creates a new instance of the binary heap which orders the elements
+    /// with a closure. The greatest element, according to the closure,
+    /// is kept at the top.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::binary_heap::BinaryHeap;
+    ///
+    /// let mut heap = BinaryHeap::with_comparator(|a: &i32, b: &i32| b.abs().cmp(&a.abs()));
+    /// heap.push(-3);
+    /// heap.push(1);
+    /// heap.push(-2);
+    ///
+    /// assert_eq!(Some(1), heap.pop());
+    /// assert_eq!(Some(-2), heap.pop());
+    /// ```
+    pub fn with_comparator(f: F) -> Self {
+        Self::from_comparator(FnComparator(f))
+    }
+}
+
+impl<T, K, F> BinaryHeap<T, KeyComparator<F>>
+where
+    K: Ord,
+    F: Fn(&T) -> K,
+{
+    /// Creates a new instance of the binary heap which orders the elements
+    /// by a key extracted with a closure. The element with the greatest key
+    /// is kept at the top.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::binary_heap::BinaryHeap;
+    ///
+    /// struct Task {
+    ///     name: &'static str,
+    ///     priority: u32,
+    /// }
+    ///
+    /// let mut heap = BinaryHeap::with_key(|t: &Task| t.priority);
+    /// heap.push(Task { name: "low", priority: 1 });
+    /// heap.push(Task { name: "high", priority: 10 });
+    ///
+    /// assert_eq!("high", heap.pop().unwrap().name);
+    /// ```
+    pub fn with_key(f: F) -> Self {
+        Self::from_comparator(KeyComparator(f))
+    }
+}
+
+impl<T, C: Compare<T>> BinaryHeap<T, C> {
+    /// Creates a new instance of the binary heap which orders
+    /// the elements with the given comparator.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::binary_heap::BinaryHeap;
+    /// use yos_collections::compare::MinComparator;
+    ///
+    /// let mut heap = BinaryHeap::from_comparator(MinComparator);
+    /// heap.push(2);
+    /// heap.push(1);
+    ///
+    /// assert_eq!(Some(1), heap.pop());
+    /// ```
+    pub fn from_comparator(cmp: C) -> Self {
+        Self {
+            data: Vec::<T>::new(),
+            cmp,
         }
     }
 
@@ -134,51 +247,184 @@ impl<T: Ord> BinaryHeap<T> {
         self.bubble_up(0, old_len);
     }
 
-    /// Bubbles up into the heap the value at 'pos' up to the 'start' index.
-    fn bubble_up(&mut self, start: usize, pos: usize) {
-        unsafe {
-            // take out the value at 'pos' and create a hole.
-            let mut mc = MemCursor::new(&mut self.data, pos);
+    /// Returns a guard which gives mutable access to the greatest element,
+    /// or `None` if the binary heap is empty. The heap is fixed when the
+    /// guard is dropped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::binary_heap::BinaryHeap;
+    ///
+    /// let mut heap = BinaryHeap::from(vec![1, 5, 3]);
+    /// if let Some(mut top) = heap.peek_mut() {
+    ///     *top = 2;
+    /// }
+    ///
+    /// assert_eq!(Some(&3), heap.peek());
+    /// ```
+    pub fn peek_mut(&mut self) -> Option<PeekMut<'_, T, C>> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(PeekMut::new(self))
+        }
+    }
 
-            while mc.pos() > start {
-                let parent = (mc.pos() - 1) / 2;
+    /// Moves all the elements of `other` into `self`, leaving `other` empty.
+    ///
+    /// The elements of the smaller heap are pushed one by one into the larger
+    /// heap, unless rebuilding the whole heap is cheaper.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::binary_heap::BinaryHeap;
+    ///
+    /// let mut a = BinaryHeap::from(vec![1, 5, 3]);
+    /// let mut b = BinaryHeap::from(vec![4, 2]);
+    /// a.append(&mut b);
+    ///
+    /// assert_eq!(5, a.len());
+    /// assert!(b.is_empty());
+    /// assert_eq!(vec![1, 2, 3, 4, 5], a.into_sorted_vec());
+    /// ```
+    pub fn append(&mut self, other: &mut Self) {
+        if self.len() < other.len() {
+            swap(self, other);
+        }
 
-                if mc.element() <= mc.get(parent) {
-                    break;
-                }
+        let start = self.data.len();
+        self.data.append(&mut other.data);
+        self.rebuild_tail(start);
+    }
 
-                mc.move_to(parent);
-            }
+    /// Retains only the elements for which the predicate returns `true`.
+    /// The heap is rebuilt if any element was removed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::binary_heap::BinaryHeap;
+    ///
+    /// let mut heap = BinaryHeap::from(vec![1, 2, 3, 4, 5, 6]);
+    /// heap.retain(|x| x % 2 == 0);
+    ///
+    /// assert_eq!(vec![2, 4, 6], heap.into_sorted_vec());
+    /// ```
+    pub fn retain<F>(&mut self, f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let len = self.len();
+        self.data.retain(f);
+        if self.len() < len {
+            self.rebuild();
         }
     }
 
+    /// Consumes the binary heap and returns a vector with the elements
+    /// sorted by the comparator in ascending priority, the element at the
+    /// top of the heap last. A max-heap returns the values in ascending
+    /// order, a min-heap in descending order. The sorting is done in place.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::binary_heap::BinaryHeap;
+    ///
+    /// let heap = BinaryHeap::from(vec![4, 1, 5, 3, 2]);
+    /// assert_eq!(vec![1, 2, 3, 4, 5], heap.into_sorted_vec());
+    ///
+    /// let mut heap = BinaryHeap::new_min();
+    /// heap.extend(vec![4, 1, 5, 3, 2]);
+    /// assert_eq!(vec![5, 4, 3, 2, 1], heap.into_sorted_vec());
+    /// ```
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        let mut end = self.len();
+        while end > 1 {
+            end -= 1;
+
+            // Move the greatest element after the heap and sift down
+            // the element which took its place.
+            self.data.swap(0, end);
+            self.sift_down_range(0, end);
+        }
+
+        self.data
+    }
+
+    /// Consumes the binary heap and returns an iterator which
+    /// yields the elements in heap order, the greatest first.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::binary_heap::BinaryHeap;
+    ///
+    /// let heap = BinaryHeap::from(vec![4, 1, 5, 3, 2]);
+    /// let xs: Vec<_> = heap.into_iter_sorted().take(2).collect();
+    ///
+    /// assert_eq!(vec![5, 4], xs);
+    /// ```
+    pub fn into_iter_sorted(self) -> IntoIterSorted<T, C> {
+        IntoIterSorted::new(self)
+    }
+
+    /// Bubbles up into the heap the value at 'pos' up to the 'start' index.
+    fn bubble_up(&mut self, start: usize, pos: usize) {
+        self.sift_up_with(start, pos, |_, _| {});
+    }
+
     /// Bubbles down into the heap the value at 'pos'
-    fn bubble_down(&mut self, pos: usize) {
+    pub(crate) fn bubble_down(&mut self, pos: usize) {
         let end = self.len();
+        self.sift_down_with(pos, end, |_, _| {});
+    }
+
+    /// Take an element at `pos` and move it down the heap,
+    /// while its children are larger.
+    fn sift_down_range(&mut self, pos: usize, end: usize) {
+        self.sift_down_with(pos, end, |_, _| {});
+    }
 
+    /// Bubbles up the value at 'pos' up to the 'start' index and returns its
+    /// new position. The hook `moved` is called with every value which moves
+    /// and its new position, the bubbled value last.
+    pub(crate) fn sift_up_with<F>(&mut self, start: usize, pos: usize, mut moved: F) -> usize
+    where
+        F: FnMut(&T, usize),
+    {
         unsafe {
+            // take out the value at 'pos' and create a hole.
             let mut mc = MemCursor::new(&mut self.data, pos);
-            let mut child = 2 * pos + 1;
-            while child < end {
-                let child1 = child + 1;
-                // compare with the greater of the two children
-                if child1 < end && (mc.get(child) <= mc.get(child1)) {
-                    child = child1;
-                }
 
-                if mc.get(child) < mc.element() {
+            while mc.pos() > start {
+                let hole = mc.pos();
+                let parent = (hole - 1) / 2;
+
+                if self.cmp.compare(mc.element(), mc.get(parent)).is_le() {
                     break;
                 }
 
-                mc.move_to(child);
-                child = 2 * mc.pos() + 1;
+                // the parent moves into the hole.
+                mc.move_to(parent);
+                moved(mc.get(hole), hole);
             }
+
+            moved(mc.element(), mc.pos());
+            mc.pos()
         }
     }
 
-    /// Take an element at `pos` and move it down the heap,
-    /// while its children are larger.
-    fn sift_down_range(&mut self, pos: usize, end: usize) {
+    /// Sifts down the value at `pos` in the first `end` values, while its
+    /// children are larger, and returns its new position. The hook `moved`
+    /// is called with every value which moves and its new position, the
+    /// sifted value last.
+    pub(crate) fn sift_down_with<F>(&mut self, pos: usize, end: usize, mut moved: F) -> usize
+    where
+        F: FnMut(&T, usize),
+    {
         unsafe {
             let mut mc = MemCursor::new(&mut self.data, pos);
 
@@ -186,17 +432,81 @@ impl<T: Ord> BinaryHeap<T> {
             while child < end {
                 let right = child + 1;
                 // compare with the greater of the two children
-                if right < end && (mc.get(child) <= mc.get(right)) {
+                if right < end && self.cmp.compare(mc.get(child), mc.get(right)).is_le() {
                     child = right;
                 }
 
                 // if we are already in order, stop.
-                if mc.get(child) < mc.element() {
+                if self.cmp.compare(mc.get(child), mc.element()).is_lt() {
                     break;
                 }
+
+                // the child moves into the hole.
+                let hole = mc.pos();
                 mc.move_to(child);
+                moved(mc.get(hole), hole);
+
                 child = 2 * mc.pos() + 1;
             }
+
+            moved(mc.element(), mc.pos());
+            mc.pos()
+        }
+    }
+
+    /// Checks that every element of the binary heap is smaller or equal
+    /// than its parent. The error names the index of the first element
+    /// which is greater than its parent.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::binary_heap::BinaryHeap;
+    ///
+    /// let heap = BinaryHeap::from(vec![1, 5, 2, 4, 3]);
+    /// assert!(heap.validate().is_ok());
+    /// ```
+    pub fn validate(&self) -> Result<(), InvariantError> {
+        for child in 1..self.len() {
+            let parent = (child - 1) / 2;
+            if self
+                .cmp
+                .compare(&self.data[child], &self.data[parent])
+                .is_gt()
+            {
+                return Err(InvariantError::Order { parent, child });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Restores the heap after the elements starting at `start` were added
+    /// at the end of the data. Either the new elements are bubbled up one
+    /// by one or the whole heap is rebuilt, whichever is cheaper.
+    pub(crate) fn rebuild_tail(&mut self, start: usize) {
+        let len = self.len();
+        if start == len {
+            return;
+        }
+
+        let tail_len = len - start;
+
+        // Rebuilding costs about 2 * len comparisons, while bubbling up
+        // the new elements costs about tail_len * log2(start) comparisons.
+        let better_to_rebuild = if start < tail_len {
+            true
+        } else {
+            let log2_start = (usize::BITS - start.leading_zeros() - 1) as usize;
+            2 * len < tail_len * log2_start
+        };
+
+        if better_to_rebuild {
+            self.rebuild();
+        } else {
+            for pos in start..len {
+                self.bubble_up(0, pos);
+            }
         }
     }
 
@@ -211,7 +521,7 @@ impl<T: Ord> BinaryHeap<T> {
     }
 }
 
-impl<T> BinaryHeap<T> {
+impl<T, C> BinaryHeap<T, C> {
     /// Returns an iterator that is visiting all values in the binary heap.
     pub fn iter(&self) -> Iter<'_, T> {
         Iter::new(self.data.iter())
@@ -219,7 +529,37 @@ impl<T> BinaryHeap<T> {
 
     /// Retruns the greatest element in the binary heap.
     pub fn peek(&self) -> Option<&T> {
-        self.data.get(0)
+        self.data.first()
+    }
+
+    /// Returns a slice with all the elements in the binary heap,
+    /// in the order they are stored.
+    pub fn as_slice(&self) -> &[T] {
+        self.data.as_slice()
+    }
+
+    /// Removes all the elements from the binary heap and returns them
+    /// in an arbitrary order. The allocated memory is kept.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::binary_heap::BinaryHeap;
+    ///
+    /// let mut heap = BinaryHeap::from(vec![1, 3, 2]);
+    /// let mut xs: Vec<_> = heap.drain().collect();
+    /// xs.sort();
+    ///
+    /// assert_eq!(vec![1, 2, 3], xs);
+    /// assert!(heap.is_empty());
+    /// ```
+    pub fn drain(&mut self) -> Drain<'_, T> {
+        Drain::new(self.data.drain(..))
+    }
+
+    /// Removes all the elements from the binary heap.
+    pub fn clear(&mut self) {
+        self.data.clear();
     }
 
     /// Returns the number of elements the binary heap can hold.
@@ -254,9 +594,39 @@ impl<T> BinaryHeap<T> {
     }
 }
 
+impl<T: Debug, C> BinaryHeap<T, C> {
+    /// Returns the implicit tree of the binary heap in the Graphviz DOT
+    /// language. Each node is labelled with its position in the storage,
+    /// the same position reported by `validate`, and its value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::binary_heap::BinaryHeap;
+    ///
+    /// let heap = BinaryHeap::from(vec![1, 3, 2]);
+    /// let dot = heap.to_dot();
+    ///
+    /// assert!(dot.starts_with("digraph BinaryHeap {"));
+    /// assert!(dot.contains("n0 -> n1;"));
+    /// assert!(dot.contains("n0 -> n2;"));
+    /// ```
+    pub fn to_dot(&self) -> String {
+        let mut dot = Dot::new("BinaryHeap");
+        for (i, item) in self.data.iter().enumerate() {
+            dot.node(i, None, item);
+            if i > 0 {
+                dot.child((i - 1) / 2, i);
+            }
+        }
+        dot.finish()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_util::lcg;
 
     #[test]
     fn new_pass() {
@@ -265,6 +635,27 @@ mod tests {
         assert!(h.is_empty());
     }
 
+    #[test]
+    fn to_dot_pass() {
+        let h = BinaryHeap::from(vec![4, 1, 3, 2]);
+        let expected = "digraph BinaryHeap {
+    node [shape=box];
+    n0 [label=\"#0\\n4\"];
+    n1 [label=\"#1\\n2\"];
+    n0 -> n1;
+    n2 [label=\"#2\\n3\"];
+    n0 -> n2;
+    n3 [label=\"#3\\n1\"];
+    n1 -> n3;
+}
+";
+        assert_eq!(expected, h.to_dot());
+        assert_eq!(
+            "digraph BinaryHeap {\n    node [shape=box];\n}\n",
+            BinaryHeap::<u8>::new().to_dot()
+        );
+    }
+
     #[test]
     fn with_capacity_pass() {
         let h = BinaryHeap::<u8>::with_capacity(10);
@@ -324,6 +715,182 @@ mod tests {
         assert_eq!(None, v);
     }
 
+    #[test]
+    fn validate_pass() {
+        let mut h = BinaryHeap::from(vec![1, 3, 5, 2, 4]);
+        assert_eq!(Ok(()), h.validate());
+
+        h.push(10);
+        h.pop();
+        assert_eq!(Ok(()), h.validate());
+    }
+
+    #[test]
+    fn validate_fail() {
+        let h = BinaryHeap {
+            data: vec![5, 4, 3, 2, 6],
+            cmp: MaxComparator,
+        };
+        assert_eq!(
+            Err(InvariantError::Order {
+                parent: 1,
+                child: 4
+            }),
+            h.validate()
+        );
+    }
+
+    #[test]
+    fn new_min_pass() {
+        let mut h = BinaryHeap::new_min();
+        for x in [4, 2, 3, 5, 1].iter() {
+            h.push(*x);
+        }
+        assert_eq!(Ok(()), h.validate());
+        assert_eq!(Some(&1), h.peek());
+
+        for x in 1..6 {
+            assert_eq!(Some(x), h.pop());
+        }
+        assert_eq!(None, h.pop());
+    }
+
+    #[test]
+    fn with_comparator_pass() {
+        let mut h = BinaryHeap::with_comparator(|a: &(u8, u8), b: &(u8, u8)| a.1.cmp(&b.1));
+        h.push((1, 3));
+        h.push((2, 1));
+        h.push((3, 2));
+
+        assert_eq!(Some((1, 3)), h.pop());
+        assert_eq!(Some((3, 2)), h.pop());
+        assert_eq!(Some((2, 1)), h.pop());
+    }
+
+    #[test]
+    fn with_key_pass() {
+        #[derive(Debug, PartialEq)]
+        struct Job {
+            id: u8,
+            priority: i32,
+        }
+
+        let mut h = BinaryHeap::with_key(|j: &Job| -j.priority);
+        for (id, priority) in [(1, 5), (2, -1), (3, 7)].iter() {
+            h.push(Job {
+                id: *id,
+                priority: *priority,
+            });
+        }
+
+        assert_eq!(Some(2), h.pop().map(|j| j.id));
+        assert_eq!(Some(1), h.pop().map(|j| j.id));
+        assert_eq!(Some(3), h.pop().map(|j| j.id));
+    }
+
+    #[test]
+    fn default_min_pass() {
+        let mut h = BinaryHeap::<u8, MinComparator>::default();
+        h.push(2);
+        h.push(1);
+        assert_eq!(Some(1), h.pop());
+    }
+
+    #[test]
+    fn append_pass() {
+        // Small into large, the elements are bubbled up.
+        let mut a = BinaryHeap::from((0..100).collect::<Vec<_>>());
+        let mut b = BinaryHeap::from(vec![150, 50, -1]);
+        a.append(&mut b);
+        assert!(b.is_empty());
+        assert_eq!(103, a.len());
+        assert_eq!(Ok(()), a.validate());
+
+        // Large into small, the heaps are swapped.
+        let mut a = BinaryHeap::from(vec![7, 3]);
+        let mut b = BinaryHeap::from((0..10).collect::<Vec<_>>());
+        a.append(&mut b);
+        assert!(b.is_empty());
+        assert_eq!(Ok(()), a.validate());
+        assert_eq!(Some(&9), a.peek());
+        assert_eq!(12, a.into_sorted_vec().len());
+    }
+
+    #[test]
+    fn rebuild_tail_pass() {
+        let mut seed = 42u64;
+        for tail in [1, 10, 100, 1000].iter() {
+            let mut h = BinaryHeap::from((0..500).map(|_| lcg(&mut seed)).collect::<Vec<_>>());
+            let start = h.len();
+            h.data.extend((0..*tail).map(|_| lcg(&mut seed)));
+            h.rebuild_tail(start);
+            assert_eq!(Ok(()), h.validate());
+        }
+    }
+
+    #[test]
+    fn retain_pass() {
+        let mut h = BinaryHeap::from((0..20).collect::<Vec<_>>());
+        h.retain(|x| x % 3 == 0);
+        assert_eq!(Ok(()), h.validate());
+        assert_eq!(vec![0, 3, 6, 9, 12, 15, 18], h.into_sorted_vec());
+    }
+
+    #[test]
+    fn drain_pass() {
+        let mut h = BinaryHeap::from(vec![4, 2, 3]);
+        let capacity = h.capacity();
+
+        let mut xs: Vec<i32> = h.drain().collect();
+        xs.sort_unstable();
+        assert_eq!(vec![2, 3, 4], xs);
+        assert!(h.is_empty());
+        assert_eq!(capacity, h.capacity());
+
+        h.push(1);
+        assert_eq!(Some(1), h.pop());
+    }
+
+    #[test]
+    fn clear_pass() {
+        let mut h = BinaryHeap::from(vec![4, 2, 3]);
+        h.clear();
+        assert!(h.is_empty());
+        assert_eq!(None, h.peek());
+    }
+
+    #[test]
+    fn into_sorted_vec_pass() {
+        let mut seed = 7u64;
+        let xs: Vec<u64> = (0..1000).map(|_| lcg(&mut seed) % 100).collect();
+        let h = BinaryHeap::from(xs.clone());
+
+        let mut ys = xs;
+        ys.sort_unstable();
+        assert_eq!(ys, h.into_sorted_vec());
+
+        let mut h = BinaryHeap::new_min();
+        h.push(1);
+        h.push(3);
+        h.push(2);
+        assert_eq!(vec![3, 2, 1], h.into_sorted_vec());
+    }
+
+    #[test]
+    fn into_iter_sorted_pass() {
+        let h = BinaryHeap::from(vec![2, 5, 1, 4, 3]);
+        let it = h.into_iter_sorted();
+        assert_eq!(5, it.len());
+        assert_eq!(vec![5, 4, 3, 2, 1], it.collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn as_slice_pass() {
+        let h = BinaryHeap::from(vec![1, 2, 3]);
+        assert_eq!(3, h.as_slice()[0]);
+        assert_eq!(3, h.as_slice().len());
+    }
+
     #[test]
     fn rebuild_pass() {
         let mut h = BinaryHeap::from(vec![1, 3, 5, 2, 4]);
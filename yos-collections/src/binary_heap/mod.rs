@@ -2,7 +2,8 @@
 mod convert;
 mod iter;
 mod main;
+mod peek_mut;
 
-pub use crate::binary_heap::convert::*;
 pub use crate::binary_heap::iter::*;
 pub use crate::binary_heap::main::BinaryHeap;
+pub use crate::binary_heap::peek_mut::PeekMut;
@@ -0,0 +1,153 @@
+//! A guard which gives mutable access to the greatest element of a binary heap.
+use super::BinaryHeap;
+use crate::compare::Compare;
+
+use core::fmt::Debug;
+use core::ops::{Deref, DerefMut};
+
+/// A guard over the greatest element of a `BinaryHeap`, returned by
+/// `BinaryHeap::peek_mut`. When the guard is dropped the element is sifted
+/// down, if it was changed, so the heap is valid again.
+///
+/// The sifting goes through a `MemCursor`, which fills back the hole when a
+/// comparison panics, so no element is lost or dropped twice. If the guard is
+/// leaked after the element was changed, the heap order may be broken.
+pub struct PeekMut<'a, T: 'a, C: 'a + Compare<T>> {
+    heap: &'a mut BinaryHeap<T, C>,
+    /// Whether the element was borrowed mutably and has to be sifted down.
+    sift: bool,
+}
+
+impl<'a, T, C: Compare<T>> PeekMut<'a, T, C> {
+    /// Creates a new guard over the greatest element of a non empty heap.
+    pub(crate) fn new(heap: &'a mut BinaryHeap<T, C>) -> Self {
+        debug_assert!(!heap.is_empty());
+        Self { heap, sift: false }
+    }
+
+    /// Removes the greatest element from the binary heap and returns it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::binary_heap::{BinaryHeap, PeekMut};
+    ///
+    /// let mut heap = BinaryHeap::from(vec![1, 3, 2]);
+    /// let top = heap.peek_mut().unwrap();
+    ///
+    /// assert_eq!(3, PeekMut::pop(top));
+    /// assert_eq!(Some(&2), heap.peek());
+    /// ```
+    pub fn pop(mut this: PeekMut<'a, T, C>) -> T {
+        // The pop re-establishes the heap, there is nothing to sift on drop.
+        this.sift = false;
+        this.heap.pop().expect("the heap is not empty")
+    }
+}
+
+impl<T, C: Compare<T>> Drop for PeekMut<'_, T, C> {
+    fn drop(&mut self) {
+        if self.sift {
+            self.heap.bubble_down(0);
+        }
+    }
+}
+
+impl<T, C: Compare<T>> Deref for PeekMut<'_, T, C> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.heap.data[0]
+    }
+}
+
+impl<T, C: Compare<T>> DerefMut for PeekMut<'_, T, C> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.sift = true;
+        &mut self.heap.data[0]
+    }
+}
+
+impl<T: Debug, C: Compare<T>> Debug for PeekMut<'_, T, C> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("PeekMut").field(&self.heap.data[0]).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::binary_heap::{BinaryHeap, PeekMut};
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    #[test]
+    fn peek_mut_pass() {
+        let mut h = BinaryHeap::from(vec![5, 1, 4, 2, 3]);
+        {
+            let mut top = h.peek_mut().unwrap();
+            assert_eq!(5, *top);
+            *top = 0;
+        }
+        assert_eq!(Ok(()), h.validate());
+        assert_eq!(Some(&4), h.peek());
+
+        // Reading through the guard does not sift.
+        {
+            let top = h.peek_mut().unwrap();
+            assert_eq!(4, *top);
+        }
+        assert_eq!(vec![0, 1, 2, 3, 4], h.into_sorted_vec());
+    }
+
+    #[test]
+    fn peek_mut_empty_pass() {
+        let mut h = BinaryHeap::<u8>::new();
+        assert!(h.peek_mut().is_none());
+    }
+
+    #[test]
+    fn peek_mut_pop_pass() {
+        let mut h = BinaryHeap::new_min();
+        h.extend(vec![3, 1, 2]);
+
+        let mut top = h.peek_mut().unwrap();
+        *top = 10;
+        assert_eq!(10, PeekMut::pop(top));
+        assert_eq!(Ok(()), h.validate());
+        assert_eq!(vec![3, 2], h.into_sorted_vec());
+    }
+
+    #[test]
+    fn peek_mut_top_k_pass() {
+        // Keep the 3 smallest elements in a max-heap.
+        let mut h = BinaryHeap::from(vec![9, 8, 7]);
+        for x in [5, 10, 1, 6, 2].iter() {
+            let mut top = h.peek_mut().unwrap();
+            if *x < *top {
+                *top = *x;
+            }
+        }
+        assert_eq!(vec![1, 2, 5], h.into_sorted_vec());
+    }
+
+    #[test]
+    fn peek_mut_panic_pass() {
+        let mut h = BinaryHeap::with_comparator(|a: &i32, b: &i32| {
+            if *a < 0 || *b < 0 {
+                panic!("negative element");
+            }
+            a.cmp(b)
+        });
+        h.extend(vec![1, 2, 3, 4]);
+
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            let mut top = h.peek_mut().unwrap();
+            *top = -1;
+        }));
+        assert!(result.is_err());
+
+        // No element was lost or duplicated.
+        let mut xs = h.as_slice().to_vec();
+        xs.sort_unstable();
+        assert_eq!(vec![-1, 1, 2, 3], xs);
+    }
+}
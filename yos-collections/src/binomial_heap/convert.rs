@@ -0,0 +1,101 @@
+use super::BinomialHeap;
+#[cfg(feature = "serde")]
+use alloc::vec::Vec;
+use core::iter::FromIterator;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+impl<T: Ord> FromIterator<T> for BinomialHeap<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> BinomialHeap<T> {
+        let mut heap = BinomialHeap::new();
+        heap.extend(iter);
+        heap
+    }
+}
+
+impl<T: Ord> Extend<T> for BinomialHeap<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|item| {
+            self.push(item);
+        });
+    }
+}
+
+impl<'a, T: 'a + Ord + Copy> Extend<&'a T> for BinomialHeap<T> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().cloned());
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: Serialize> Serialize for BinomialHeap<T> {
+    /// Serializes the elements as a sequence, in arbitrary order.
+    /// The handles are not serialized.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: Ord + Deserialize<'de>> Deserialize<'de> for BinomialHeap<T> {
+    /// Deserializes a sequence of elements, which are pushed one by one
+    /// into a new heap. The elements get new handles.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::deserialize(deserializer).map(|xs: Vec<T>| xs.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_iterator() {
+        let xs = vec![3, 1, 4];
+        let mut h = BinomialHeap::from_iter(xs);
+
+        h.push(2);
+        assert_eq!(4, h.len());
+        assert_eq!(Some(4), h.pop());
+    }
+
+    #[test]
+    fn extend() {
+        let mut h: BinomialHeap<i32> = vec![3, 1].into_iter().collect();
+        h.extend(vec![4, 2]);
+        h.extend(&[5, 0]);
+
+        assert_eq!(6, h.len());
+        for x in (0..6).rev() {
+            assert_eq!(Some(x), h.pop());
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serialize_pass() {
+        use serde_test::{assert_ser_tokens, Token};
+
+        let h: BinomialHeap<u8> = vec![7].into_iter().collect();
+        assert_ser_tokens(
+            &h,
+            &[Token::Seq { len: Some(1) }, Token::U8(7), Token::SeqEnd],
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip_pass() {
+        use serde::de::value::{Error, SeqDeserializer};
+        use serde::de::IntoDeserializer;
+
+        let mut h: BinomialHeap<i32> = (0..50).collect();
+        h.pop();
+
+        let xs: Vec<i32> = h.iter().copied().collect();
+        let de: SeqDeserializer<_, Error> = xs.into_deserializer();
+        let g = BinomialHeap::<i32>::deserialize(de).unwrap();
+        assert_eq!(Ok(()), g.validate());
+        assert_eq!(h.into_sorted_vec(), g.into_sorted_vec());
+    }
+}
@@ -0,0 +1,144 @@
+//! Iterators over the elements in a binomial heap.
+use super::main::Slot;
+use super::BinomialHeap;
+
+use core::fmt::Debug;
+use core::iter::FusedIterator;
+
+crate::handle_heap::iterators!(BinomialHeap, Slot);
+
+/// A draining iterator over the elements of a `BinomialHeap`,
+/// which yields the elements in descending order.
+///
+/// The elements which are not consumed are removed from the heap
+/// when the iterator is dropped.
+pub struct DrainSorted<'a, T: Ord> {
+    heap: &'a mut BinomialHeap<T>,
+}
+
+impl<'a, T: Ord> DrainSorted<'a, T> {
+    /// Creates a new instance of the draining iterator.
+    pub(crate) fn new(heap: &'a mut BinomialHeap<T>) -> Self {
+        Self { heap }
+    }
+}
+
+impl<T: Ord + Debug> Debug for DrainSorted<'_, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("DrainSorted").field(&self.heap).finish()
+    }
+}
+
+impl<T: Ord> Drop for DrainSorted<'_, T> {
+    fn drop(&mut self) {
+        self.heap.clear();
+    }
+}
+
+impl<T: Ord> Iterator for DrainSorted<'_, T> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        self.heap.pop()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.heap.len(), Some(self.heap.len()))
+    }
+}
+
+impl<T: Ord> ExactSizeIterator for DrainSorted<'_, T> {}
+
+impl<T: Ord> FusedIterator for DrainSorted<'_, T> {}
+
+impl<T> IntoIterator for BinomialHeap<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter::new(self.nodes.into_iter(), self.len)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a BinomialHeap<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::binomial_heap::BinomialHeap;
+
+    #[test]
+    fn iter_pass() {
+        let h: BinomialHeap<i32> = (0..100).collect();
+
+        let it = h.iter();
+        assert_eq!(100, it.len());
+
+        let mut xs: Vec<i32> = it.copied().collect();
+        xs.sort_unstable();
+        assert_eq!((0..100).collect::<Vec<_>>(), xs);
+
+        // The heap is still intact.
+        assert_eq!(100, h.len());
+        assert_eq!(Some(&99), h.peek());
+    }
+
+    #[test]
+    fn iter_empty_pass() {
+        let h = BinomialHeap::<i32>::new();
+        assert!(h.iter().next().is_none());
+        assert_eq!(0, (&h).into_iter().count());
+    }
+
+    #[test]
+    fn iter_debug_pass() {
+        let h: BinomialHeap<i32> = (0..3).collect();
+        let s = format!("{:?}", h.iter());
+        assert!(s.starts_with("Iter"));
+    }
+
+    #[test]
+    fn into_iter_pass() {
+        let h: BinomialHeap<i32> = (0..37).collect();
+
+        let mut it = h.into_iter();
+        assert_eq!((37, Some(37)), it.size_hint());
+        it.next();
+        assert_eq!(36, it.len());
+
+        let s = format!("{:?}", it);
+        assert!(s.starts_with("IntoIter"));
+
+        let mut xs: Vec<i32> = it.collect();
+        xs.sort_unstable();
+        assert_eq!(36, xs.len());
+        xs.dedup();
+        assert_eq!(36, xs.len());
+    }
+
+    #[test]
+    fn drain_sorted_pass() {
+        let mut h: BinomialHeap<i32> = vec![3, 1, 4, 1, 5, 9, 2, 6].into_iter().collect();
+
+        let xs: Vec<i32> = h.drain_sorted().collect();
+        assert_eq!(vec![9, 6, 5, 4, 3, 2, 1, 1], xs);
+        assert!(h.is_empty());
+    }
+
+    #[test]
+    fn drain_sorted_partial_pass() {
+        let mut h: BinomialHeap<i32> = (0..10).collect();
+
+        let xs: Vec<i32> = h.drain_sorted().take(2).collect();
+        assert_eq!(vec![9, 8], xs);
+        assert!(h.is_empty());
+    }
+}
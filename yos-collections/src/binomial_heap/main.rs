@@ -0,0 +1,1278 @@
+//! Implementation of the binomial heap
+use super::iter::*;
+use crate::arena::Handle;
+use crate::dot::Dot;
+use crate::invariant::InvariantError;
+use crate::pool::{Key, Pool, Rename};
+
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::Debug;
+use core::mem;
+
+/// A node of the binomial heap. The nodes are stored in a pool and
+/// are linked through their keys.
+#[derive(Clone)]
+pub(crate) struct Slot<T> {
+    /// The stored value.
+    pub(crate) item: T,
+    /// The key of the handle of the stored value.
+    pub(crate) handle: Key,
+    /// The order of the node, we have 2^order elements
+    /// in the tree with the root in this node.
+    pub(crate) order: usize,
+    /// Optional parent node, the roots don't have a parent.
+    pub(crate) parent: Option<Key>,
+    /// Optional sibling node
+    pub(crate) sibling: Option<Key>,
+    /// Optional child node, the children are kept in descending order.
+    pub(crate) child: Option<Key>,
+}
+
+impl<T> Slot<T> {
+    /// Replaces the links of a cloned node by the keys of the cloned nodes.
+    /// The handle is a key of another pool, the caller updates it.
+    fn relink(&mut self, rename: &Rename) {
+        self.parent = rename.link(self.parent);
+        self.sibling = rename.link(self.sibling);
+        self.child = rename.link(self.child);
+    }
+}
+
+/// Implementation of the binomial heap.
+pub struct BinomialHeap<T> {
+    /// The nodes of the heap.
+    pub(crate) nodes: Pool<Slot<T>>,
+    /// For each handle, the key of the node which stores its value.
+    handles: Pool<Key>,
+    /// The first node in the chain of roots, in ascending order.
+    pub(crate) root: Option<Key>,
+    pub(crate) len: usize,
+}
+
+impl<T: Clone> Clone for BinomialHeap<T> {
+    fn clone(&self) -> Self {
+        // The clones of the pools get new keys, the nodes and the handles
+        // link to each other through them.
+        let (mut nodes, node_keys) = self.nodes.clone_renamed();
+        let (mut handles, handle_keys) = self.handles.clone_renamed();
+        nodes.values_mut().for_each(|slot| {
+            slot.relink(&node_keys);
+            slot.handle = handle_keys.key(slot.handle);
+        });
+        handles.values_mut().for_each(|x| *x = node_keys.key(*x));
+
+        Self {
+            nodes,
+            handles,
+            root: node_keys.link(self.root),
+            len: self.len,
+        }
+    }
+}
+
+impl<T: Ord> Default for BinomialHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Debug> Debug for BinomialHeap<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: Ord> BinomialHeap<T> {
+    /// Creates a new instance of the binomial heap.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::binomial_heap::BinomialHeap;
+    ///
+    /// let mut heap = BinomialHeap::new();
+    /// heap.push(1);
+    /// heap.push(3);
+    /// heap.push(2);
+    ///
+    /// assert_eq!(3, heap.len());
+    /// ```
+    pub fn new() -> Self {
+        Self {
+            nodes: Pool::new(),
+            handles: Pool::new(),
+            root: None,
+            len: 0,
+        }
+    }
+
+    /// Creates a new instance of the binomial heap with a given capacity.
+    /// Pushing up to `capacity` elements does not reallocate the storage.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::binomial_heap::BinomialHeap;
+    ///
+    /// let mut heap = BinomialHeap::with_capacity(3);
+    /// heap.push(1);
+    /// heap.push(3);
+    /// heap.push(2);
+    ///
+    /// assert_eq!(3, heap.len());
+    /// assert!(heap.capacity() >= 3);
+    /// ```
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            nodes: Pool::with_capacity(capacity),
+            handles: Pool::with_capacity(capacity),
+            root: None,
+            len: 0,
+        }
+    }
+
+    /// Returns the greatest element in the binomial heap,
+    /// or `None` if it is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::binomial_heap::BinomialHeap;
+    ///
+    /// let mut heap = BinomialHeap::new();
+    /// assert_eq!(None, heap.peek());
+    ///
+    /// heap.push(1);
+    /// heap.push(3);
+    /// heap.push(2);
+    ///
+    /// assert_eq!(Some(&3), heap.peek());
+    /// ```
+    pub fn peek(&self) -> Option<&T> {
+        self.max_root().map(|x| &self.slot(x).item)
+    }
+
+    /// Pushes a value into the binomial heap and returns its handle.
+    /// The handle can be used later to update or remove the value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::binomial_heap::BinomialHeap;
+    ///
+    /// let mut heap = BinomialHeap::new();
+    /// heap.push(1);
+    /// let h = heap.push(3);
+    /// heap.push(2);
+    ///
+    /// assert_eq!(Some(&3), heap.get(h));
+    ///
+    /// let v = heap.pop().unwrap();
+    /// assert_eq!(3, v);
+    /// ```
+    pub fn push(&mut self, item: T) -> Handle {
+        let handle = self.handles.insert(self.nodes.vacant_key());
+        let x = self.insert(item, handle);
+        debug_assert!(self.handles.get(handle) == Some(&x));
+
+        self.handles.handle(handle)
+    }
+
+    /// Removes the greatest item from the binomial heap and returns it,
+    /// or `None` if it is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::binomial_heap::BinomialHeap;
+    ///
+    /// let mut heap = BinomialHeap::new();
+    ///
+    /// let v = heap.pop();
+    /// assert_eq!(None, v);
+    ///
+    /// heap.push(1);
+    /// heap.push(3);
+    /// heap.push(2);
+    ///
+    /// let v = heap.pop().unwrap();
+    /// assert_eq!(3, v);
+    /// ```
+    pub fn pop(&mut self) -> Option<T> {
+        self.max_root().map(|x| {
+            let (item, handle) = self.remove_root(x);
+            self.handles.remove(handle);
+            item
+        })
+    }
+
+    /// Moves all the elements of `other` into `self`, leaving `other` empty.
+    ///
+    /// The storage of `other` is taken over without moving its nodes, then
+    /// the chains of roots are melded in `O(log n)` time. The handles of both
+    /// heaps stay valid and name their elements in `self`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::binomial_heap::BinomialHeap;
+    ///
+    /// let mut a = BinomialHeap::new();
+    /// a.push(1);
+    /// let h = a.push(5);
+    ///
+    /// let mut b = BinomialHeap::new();
+    /// let g = b.push(7);
+    ///
+    /// a.append(&mut b);
+    ///
+    /// assert_eq!(3, a.len());
+    /// assert!(b.is_empty());
+    /// assert_eq!(Some(&7), a.peek());
+    /// assert_eq!(Some(&5), a.get(h));
+    /// assert_eq!(Some(&7), a.get(g));
+    /// ```
+    pub fn append(&mut self, other: &mut Self) {
+        let other_root = other.root.take();
+        self.len += mem::take(&mut other.len);
+        self.nodes.append(&mut other.nodes);
+        self.handles.append(&mut other.handles);
+
+        let root = self.merge(self.root, other_root);
+        self.root = self.coalesce(root);
+    }
+
+    /// Consumes the binomial heap and returns a vector with all
+    /// the elements in descending order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::binomial_heap::BinomialHeap;
+    ///
+    /// let heap: BinomialHeap<_> = vec![1, 5, 2, 4, 3].into_iter().collect();
+    ///
+    /// assert_eq!(vec![5, 4, 3, 2, 1], heap.into_sorted_vec());
+    /// ```
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        let mut xs = Vec::with_capacity(self.len);
+        while let Some(item) = self.pop() {
+            xs.push(item);
+        }
+        xs
+    }
+
+    /// Clears the binomial heap, returning an iterator over the removed
+    /// elements in descending order. The elements which are not consumed
+    /// by the iterator are dropped when the iterator is dropped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::binomial_heap::BinomialHeap;
+    ///
+    /// let mut heap: BinomialHeap<_> = vec![1, 5, 2, 4, 3].into_iter().collect();
+    ///
+    /// let xs: Vec<_> = heap.drain_sorted().collect();
+    /// assert_eq!(vec![5, 4, 3, 2, 1], xs);
+    /// assert!(heap.is_empty());
+    /// ```
+    pub fn drain_sorted(&mut self) -> DrainSorted<'_, T> {
+        DrainSorted::new(self)
+    }
+
+    /// Replaces the value of the element with the given handle by
+    /// a greater or equal value, and moves the element up in its tree.
+    /// The operation takes `O(log n)` time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the handle is not in the heap or if the new value
+    /// is smaller than the current one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::binomial_heap::BinomialHeap;
+    ///
+    /// let mut heap = BinomialHeap::new();
+    /// heap.push(5);
+    /// let h = heap.push(1);
+    ///
+    /// heap.increase_key(h, 10);
+    /// assert_eq!(Some(&10), heap.peek());
+    /// ```
+    pub fn increase_key(&mut self, handle: Handle, item: T) {
+        let x = self.node_of(handle).expect("the handle is not in the heap");
+        assert!(
+            item >= self.slot(x).item,
+            "the new value is smaller than the current one"
+        );
+
+        self.slot_mut(x).item = item;
+        self.sift_up(x, false);
+    }
+
+    /// Replaces the value of the element with the given handle by
+    /// a smaller or equal value. The element is taken out of its tree
+    /// and inserted back, keeping its handle. The operation takes `O(log n)` time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the handle is not in the heap or if the new value
+    /// is greater than the current one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::binomial_heap::BinomialHeap;
+    ///
+    /// let mut heap = BinomialHeap::new();
+    /// let h = heap.push(10);
+    /// heap.push(5);
+    ///
+    /// heap.decrease_key(h, 1);
+    /// assert_eq!(Some(&5), heap.peek());
+    /// assert_eq!(Some(&1), heap.get(h));
+    /// ```
+    pub fn decrease_key(&mut self, handle: Handle, item: T) {
+        let x = self.node_of(handle).expect("the handle is not in the heap");
+        assert!(
+            item <= self.slot(x).item,
+            "the new value is greater than the current one"
+        );
+
+        let x = self.sift_up(x, true);
+        let (_, handle) = self.remove_root(x);
+
+        let x = self.insert(item, handle);
+        *self.handles.get_mut(handle).unwrap() = x;
+    }
+
+    /// Removes the element with the given handle from the binomial heap
+    /// and returns it, or `None` if the handle is not in the heap.
+    /// The operation takes `O(log n)` time.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::binomial_heap::BinomialHeap;
+    ///
+    /// let mut heap = BinomialHeap::new();
+    /// heap.push(1);
+    /// let h = heap.push(3);
+    /// heap.push(2);
+    ///
+    /// assert_eq!(Some(3), heap.remove(h));
+    /// assert_eq!(None, heap.remove(h));
+    /// assert_eq!(Some(2), heap.pop());
+    /// ```
+    pub fn remove(&mut self, handle: Handle) -> Option<T> {
+        self.node_of(handle).map(|x| {
+            let x = self.sift_up(x, true);
+            let (item, handle) = self.remove_root(x);
+            self.handles.remove(handle);
+            item
+        })
+    }
+
+    /// Checks the invariants of the binomial heap: every child is smaller or
+    /// equal than its parent and links back to it, the orders along the chain
+    /// of roots are strictly increasing, a node of order `k` has `k` children
+    /// of orders `k - 1` down to `0`, and the length matches the number of
+    /// elements. The error names the nodes by their depth-first position, the
+    /// roots in the order of the chain, each node before its children and the
+    /// children before the next sibling.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::binomial_heap::BinomialHeap;
+    ///
+    /// let heap: BinomialHeap<_> = vec![1, 5, 2, 4, 3].into_iter().collect();
+    /// assert!(heap.validate().is_ok());
+    /// ```
+    pub fn validate(&self) -> Result<(), InvariantError> {
+        let mut position = 0;
+        let mut previous: Option<usize> = None;
+
+        let mut root = self.root;
+        while let Some(r) = root {
+            let slot = self.slot(r);
+            if slot.parent.is_some() {
+                return Err(InvariantError::Parent { node: position });
+            }
+
+            if let Some(previous) = previous.filter(|previous| slot.order <= *previous) {
+                return Err(InvariantError::RootOrder {
+                    node: position,
+                    order: slot.order,
+                    previous,
+                });
+            }
+            previous = Some(slot.order);
+
+            position = self.validate_tree(r, position)?;
+            root = slot.sibling;
+        }
+
+        // Every stored node has to be reachable from the roots.
+        let found = if position != self.len {
+            position
+        } else {
+            self.nodes.len()
+        };
+        if found != self.len {
+            return Err(InvariantError::Length {
+                expected: self.len,
+                found,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Validates the tree with the root in `r`, whose depth-first position is
+    /// `position`, and returns the position after its last node.
+    fn validate_tree(&self, r: Key, mut position: usize) -> Result<usize, InvariantError> {
+        let mut stack: Vec<(Key, Option<(Key, usize)>)> = vec![(r, None)];
+
+        while let Some((x, parent)) = stack.pop() {
+            let id = position;
+            position += 1;
+            let slot = self.slot(x);
+
+            if let Some((p, parent)) = parent {
+                if slot.parent != Some(p) {
+                    return Err(InvariantError::Parent { node: id });
+                }
+
+                if slot.item > self.slot(p).item {
+                    return Err(InvariantError::Order { parent, child: id });
+                }
+            }
+
+            let mut children = Vec::new();
+            let mut child = slot.child;
+            while let Some(c) = child {
+                let cs = self.slot(c);
+
+                // The children are kept in descending order.
+                let count = children.len();
+                if count < slot.order && cs.order != slot.order - count - 1 {
+                    return Err(InvariantError::ChildOrder {
+                        node: id,
+                        expected: slot.order - count - 1,
+                        found: cs.order,
+                    });
+                }
+
+                children.push(c);
+                child = cs.sibling;
+            }
+
+            if children.len() != slot.order {
+                return Err(InvariantError::ChildCount {
+                    node: id,
+                    order: slot.order,
+                    children: children.len(),
+                });
+            }
+
+            // Visit the children in their order.
+            stack.extend(children.into_iter().rev().map(|c| (c, Some((x, id)))));
+        }
+
+        Ok(position)
+    }
+
+    /// Inserts a value with the given handle as a new tree of order 0 and
+    /// returns the key of its node.
+    fn insert(&mut self, item: T, handle: Key) -> Key {
+        let x = self.nodes.insert(Slot {
+            item,
+            handle,
+            order: 0,
+            parent: None,
+            sibling: None,
+            child: None,
+        });
+        self.len += 1;
+
+        let root = self.merge(self.root, Some(x));
+        self.root = self.coalesce(root);
+        x
+    }
+
+    /// Returns the root with the greatest value. Each of the trees in the
+    /// chain of roots is a max-heap, so we just need to walk the chain.
+    fn max_root(&self) -> Option<Key> {
+        self.root.map(|mut max| {
+            let mut current = self.slot(max).sibling;
+            while let Some(x) = current {
+                if self.slot(x).item > self.slot(max).item {
+                    max = x;
+                }
+                current = self.slot(x).sibling;
+            }
+            max
+        })
+    }
+
+    /// Moves the value at `x` up in its tree, swapping it with its parent,
+    /// while it is greater than the parent's value. When `force` is set the
+    /// value is moved all the way up to the root. Returns the final position.
+    fn sift_up(&mut self, mut x: Key, force: bool) -> Key {
+        while let Some(p) = self.slot(x).parent {
+            if !force && self.slot(p).item >= self.slot(x).item {
+                break;
+            }
+
+            self.swap_items(x, p);
+            x = p;
+        }
+        x
+    }
+
+    /// Removes the root `x` from the chain of roots and melds its children
+    /// back into the heap. Returns the removed value and its handle.
+    fn remove_root(&mut self, x: Key) -> (T, Key) {
+        debug_assert!(self.slot(x).parent.is_none());
+
+        // Unlink the node from the chain of roots.
+        let sibling = self.slot(x).sibling;
+        if self.root == Some(x) {
+            self.root = sibling;
+        } else {
+            let mut prev = self.root.unwrap();
+            while self.slot(prev).sibling != Some(x) {
+                prev = self.slot(prev).sibling.unwrap();
+            }
+            self.slot_mut(prev).sibling = sibling;
+        }
+
+        // The children are in descending order, reverse them into
+        // an ascending chain of roots.
+        let mut children = None;
+        let mut current = self.slot(x).child;
+        while let Some(c) = current {
+            let slot = self.slot_mut(c);
+            current = slot.sibling;
+            slot.parent = None;
+            slot.sibling = children;
+            children = Some(c);
+        }
+
+        let root = self.merge(self.root, children);
+        self.root = self.coalesce(root);
+
+        let slot = self.nodes.remove(x);
+        self.len -= 1;
+
+        (slot.item, slot.handle)
+    }
+
+    /// Merges two chains of roots, each of them in ascending order,
+    /// into a single chain in ascending order.
+    fn merge(&mut self, mut a: Option<Key>, mut b: Option<Key>) -> Option<Key> {
+        let mut head = None;
+        let mut tail: Option<Key> = None;
+
+        loop {
+            let next = match (a, b) {
+                (None, None) => break,
+                (Some(x), None) | (None, Some(x)) => {
+                    a = None;
+                    b = None;
+                    x
+                }
+                (Some(x), Some(y)) => {
+                    if self.slot(x).order <= self.slot(y).order {
+                        a = self.slot(x).sibling;
+                        x
+                    } else {
+                        b = self.slot(y).sibling;
+                        y
+                    }
+                }
+            };
+
+            match tail {
+                None => head = Some(next),
+                Some(t) => self.slot_mut(t).sibling = Some(next),
+            }
+            tail = Some(next);
+
+            // The rest of the remaining chain is already linked.
+            if a.is_none() && b.is_none() {
+                break;
+            }
+        }
+
+        head
+    }
+
+    /// Coalesce any two trees in the chain of roots which have the same
+    /// order, such that we restore the binomial property of the heap.
+    /// Returns the new head of the chain.
+    fn coalesce(&mut self, head: Option<Key>) -> Option<Key> {
+        let mut head = head?;
+        let mut prev: Option<Key> = None;
+        let mut x = head;
+
+        while let Some(next) = self.slot(x).sibling {
+            let order = self.slot(x).order;
+            let next_sibling = self.slot(next).sibling;
+
+            if order != self.slot(next).order
+                || next_sibling.is_some_and(|s| self.slot(s).order == order)
+            {
+                // Keep moving, the trees don't need to be coalesced yet.
+                prev = Some(x);
+                x = next;
+            } else if self.slot(x).item >= self.slot(next).item {
+                // Coalesce under the current node.
+                self.slot_mut(x).sibling = next_sibling;
+                self.link(x, next);
+            } else {
+                // Coalesce under the sibling.
+                match prev {
+                    None => head = next,
+                    Some(p) => self.slot_mut(p).sibling = Some(next),
+                }
+                self.link(next, x);
+                x = next;
+            }
+        }
+
+        Some(head)
+    }
+
+    /// Links the tree rooted in `b` as the first child of `a`.
+    fn link(&mut self, a: Key, b: Key) {
+        debug_assert!(self.slot(a).order == self.slot(b).order);
+        debug_assert!(self.slot(a).item >= self.slot(b).item);
+
+        let child = self.slot(a).child;
+        let slot = self.slot_mut(b);
+        slot.parent = Some(a);
+        slot.sibling = child;
+
+        let slot = self.slot_mut(a);
+        slot.child = Some(b);
+        slot.order += 1;
+    }
+}
+
+impl<T> BinomialHeap<T> {
+    /// Returns an iterator that is visiting all values in the binomial heap,
+    /// in arbitrary order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::binomial_heap::BinomialHeap;
+    ///
+    /// let heap: BinomialHeap<_> = vec![1, 2, 3].into_iter().collect();
+    ///
+    /// let sum: i32 = heap.iter().sum();
+    /// assert_eq!(6, sum);
+    /// ```
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter::new(self.nodes.iter(), self.len)
+    }
+
+    /// Returns the value of the element with the given handle,
+    /// or `None` if the handle is not in the heap.
+    pub fn get(&self, handle: Handle) -> Option<&T> {
+        self.node_of(handle).map(|x| &self.slot(x).item)
+    }
+
+    /// Returns a flag indicating whenever the handle is in the heap.
+    pub fn contains(&self, handle: Handle) -> bool {
+        self.node_of(handle).is_some()
+    }
+
+    /// Returns the number of elements in the binomial heap.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns a flag indicating whenever the binomial heap is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Drops all the elements from the binomial heap.
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+        self.handles.clear();
+        self.root = None;
+        self.len = 0;
+    }
+
+    /// Returns the number of elements the binomial heap can hold
+    /// without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.nodes.capacity().min(self.handles.capacity())
+    }
+
+    /// Reserves capacity for at least `additional` more elements
+    /// to be inserted in the binomial heap.
+    pub fn reserve(&mut self, additional: usize) {
+        self.nodes.reserve(additional);
+        self.handles.reserve(additional);
+    }
+
+    /// Reserves the minimum capacity for exactly `additional` more elements
+    /// to be inserted in the binomial heap.
+    pub fn reserve_exact(&mut self, additional: usize) {
+        self.nodes.reserve_exact(additional);
+        self.handles.reserve_exact(additional);
+    }
+
+    /// Shrinks the allocated memory. The handles of the elements
+    /// in the heap remain valid.
+    pub fn shrink_to_fit(&mut self) {
+        self.nodes.shrink_to_fit();
+        self.handles.shrink_to_fit();
+    }
+
+    /// Returns the node which stores the value of the handle.
+    fn node_of(&self, handle: Handle) -> Option<Key> {
+        let h = self.handles.key(handle)?;
+        self.handles.get(h).copied()
+    }
+
+    fn slot(&self, x: Key) -> &Slot<T> {
+        self.nodes.get(x).expect("the node is vacant")
+    }
+
+    fn slot_mut(&mut self, x: Key) -> &mut Slot<T> {
+        self.nodes.get_mut(x).expect("the node is vacant")
+    }
+
+    /// Swaps the values, together with their handles, of two nodes.
+    fn swap_items(&mut self, a: Key, b: Key) {
+        let (x, y) = self.nodes.get2_mut(a, b);
+        mem::swap(&mut x.item, &mut y.item);
+        mem::swap(&mut x.handle, &mut y.handle);
+
+        let (hx, hy) = (x.handle, y.handle);
+        *self.handles.get_mut(hx).unwrap() = a;
+        *self.handles.get_mut(hy).unwrap() = b;
+    }
+}
+
+impl<T: Debug> BinomialHeap<T> {
+    /// Returns the forest of the binomial heap in the Graphviz DOT language.
+    /// Each node is labelled with its depth-first position, the same position
+    /// reported by `validate`, its order and its value. The solid edges go from a parent
+    /// to its children and the dashed edges link the siblings, the roots
+    /// included.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::binomial_heap::BinomialHeap;
+    ///
+    /// let mut heap = BinomialHeap::new();
+    /// heap.push(1);
+    /// heap.push(2);
+    /// let dot = heap.to_dot();
+    ///
+    /// assert!(dot.starts_with("digraph BinomialHeap {"));
+    /// assert!(dot.contains("n0 -> n1;"));
+    /// ```
+    pub fn to_dot(&self) -> String {
+        let mut dot = Dot::new("BinomialHeap");
+        let mut roots = Vec::new();
+        let mut position = 0;
+        let mut stack: Vec<(Key, Option<usize>, Option<usize>)> =
+            self.root.map(|r| (r, None, None)).into_iter().collect();
+
+        while let Some((x, parent, previous)) = stack.pop() {
+            let slot = self.slot(x);
+            let id = position;
+            position += 1;
+            dot.node(id, Some(slot.order), &slot.item);
+
+            match parent {
+                Some(parent) => dot.child(parent, id),
+                None => roots.push(id),
+            }
+            if let Some(previous) = previous {
+                dot.sibling(previous, id);
+            }
+
+            // The sibling comes after the subtree of the node.
+            if let Some(sibling) = slot.sibling {
+                stack.push((sibling, parent, Some(id)));
+            }
+            if let Some(child) = slot.child {
+                stack.push((child, Some(id), None));
+            }
+        }
+
+        dot.same_rank(&roots);
+        dot.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::lcg;
+
+    #[test]
+    fn new_pass() {
+        let h = BinomialHeap::<u8>::new();
+        assert_eq!(h.len(), 0);
+        assert!(h.is_empty());
+        assert!(h.peek().is_none());
+    }
+
+    #[test]
+    fn default_pass() {
+        let h = BinomialHeap::<u8>::default();
+        assert_eq!(h.len(), 0);
+        assert!(h.is_empty());
+    }
+
+    #[test]
+    fn debug_pass() {
+        let mut h = BinomialHeap::<u8>::new();
+        h.push(1);
+        h.push(2);
+        h.push(3);
+
+        let s = format!("{:?}", h);
+        assert!(s.starts_with('['));
+        assert!(s.contains('1') && s.contains('2') && s.contains('3'));
+    }
+
+    #[test]
+    fn push_pass() {
+        let mut h = BinomialHeap::<u8>::new();
+        h.push(4);
+        h.push(2);
+        h.push(3);
+        h.push(5);
+
+        assert_eq!(h.len(), 4);
+        assert!(!h.is_empty());
+        assert_eq!(Some(&5), h.peek());
+    }
+
+    #[test]
+    fn to_dot_pass() {
+        let mut h = BinomialHeap::new();
+        h.push('a');
+        h.push('b');
+        h.push('c');
+
+        let expected = "digraph BinomialHeap {
+    node [shape=box];
+    n0 [label=\"#0 o0\\n'c'\"];
+    n1 [label=\"#1 o1\\n'b'\"];
+    n0 -> n1 [style=dashed, constraint=false];
+    n2 [label=\"#2 o0\\n'a'\"];
+    n1 -> n2;
+    { rank=same; n0; n1; }
+}
+";
+        assert_eq!(expected, h.to_dot());
+    }
+
+    #[test]
+    fn pop_pass() {
+        let mut h = BinomialHeap::<u8>::new();
+        h.push(4);
+        h.push(2);
+        h.push(3);
+        h.push(5);
+
+        assert_eq!(Some(5), h.pop());
+        assert_eq!(Some(4), h.pop());
+        assert_eq!(Some(3), h.pop());
+        assert_eq!(Some(2), h.pop());
+        assert_eq!(None, h.pop());
+        assert_eq!(0, h.len());
+    }
+
+    #[test]
+    fn pop_random_pass() {
+        let mut seed = 17;
+        let mut xs: Vec<u64> = (0..1000).map(|_| lcg(&mut seed) % 100).collect();
+
+        let mut h = BinomialHeap::new();
+        for x in xs.iter() {
+            h.push(*x);
+        }
+        assert_eq!(xs.len(), h.len());
+
+        xs.sort_unstable_by(|a, b| b.cmp(a));
+        for x in xs {
+            assert_eq!(Some(x), h.pop());
+        }
+        assert!(h.is_empty());
+    }
+
+    #[test]
+    fn append_pass() {
+        let mut a = BinomialHeap::new();
+        let mut b = BinomialHeap::new();
+        for x in 0..10 {
+            a.push(x);
+        }
+        for x in 10..17 {
+            b.push(x);
+        }
+
+        a.append(&mut b);
+        assert_eq!(17, a.len());
+        assert!(b.is_empty());
+        assert!(b.peek().is_none());
+
+        for x in (0..17).rev() {
+            assert_eq!(Some(x), a.pop());
+        }
+    }
+
+    #[test]
+    fn append_handles_pass() {
+        // The handles of both heaps stay valid through several melds, and
+        // the key updates move the values between the nodes of both heaps.
+        let mut heaps: Vec<BinomialHeap<u64>> = Vec::new();
+        let mut live: Vec<(Handle, u64)> = Vec::new();
+        for i in 0..4 {
+            let mut h = BinomialHeap::new();
+            for x in 0..(5 + i * 3) {
+                let x = 100 + x * 10 + i;
+                live.push((h.push(x), x));
+            }
+            heaps.push(h);
+        }
+
+        let mut b = heaps.pop().unwrap();
+        b.append(&mut heaps.pop().unwrap());
+        let mut a = heaps.pop().unwrap();
+        a.append(&mut heaps.pop().unwrap());
+        a.append(&mut b);
+        assert_eq!(Ok(()), a.validate());
+
+        for (i, (handle, x)) in live.iter_mut().enumerate() {
+            assert_eq!(Some(&*x), a.get(*handle));
+            if i % 2 == 1 {
+                *x += 1000;
+                a.increase_key(*handle, *x);
+            } else {
+                *x -= 5;
+                a.decrease_key(*handle, *x);
+            }
+        }
+        assert_eq!(Ok(()), a.validate());
+
+        // The clone gets its own handles.
+        let c = a.clone();
+        assert_eq!(Ok(()), c.validate());
+        assert_eq!(None, c.get(live[0].0));
+
+        live.sort_unstable_by_key(|(_, x)| *x);
+        while let Some((handle, x)) = live.pop() {
+            assert_eq!(Some(&x), a.get(handle));
+            assert_eq!(Some(x), a.pop());
+            assert!(!a.contains(handle));
+        }
+        assert_eq!(c.len(), c.into_sorted_vec().len());
+    }
+
+    #[test]
+    fn append_empty_pass() {
+        let mut a = BinomialHeap::new();
+        let mut b = BinomialHeap::new();
+        b.push(1);
+
+        a.append(&mut b);
+        assert_eq!(1, a.len());
+        assert_eq!(Some(&1), a.peek());
+
+        a.append(&mut b);
+        assert_eq!(1, a.len());
+    }
+
+    #[test]
+    fn clear_pass() {
+        let mut h = BinomialHeap::new();
+        h.push(1);
+        h.push(2);
+
+        h.clear();
+        assert!(h.is_empty());
+        assert!(h.pop().is_none());
+    }
+
+    #[test]
+    fn into_sorted_vec_pass() {
+        let mut seed = 5;
+        let mut xs: Vec<u64> = (0..500).map(|_| lcg(&mut seed) % 50).collect();
+
+        let h: BinomialHeap<u64> = xs.iter().copied().collect();
+        xs.sort_unstable_by(|a, b| b.cmp(a));
+        assert_eq!(xs, h.into_sorted_vec());
+    }
+
+    #[test]
+    fn drop_large_heap_pass() {
+        let h: BinomialHeap<u32> = (0..2_000_000).collect();
+        assert_eq!(2_000_000, h.len());
+        drop(h);
+    }
+
+    #[test]
+    fn clone_large_heap_pass() {
+        let mut h: BinomialHeap<u32> = (0..1_000_000).collect();
+
+        let mut c = h.clone();
+        assert_eq!(h.len(), c.len());
+        assert_eq!(Some(999_999), c.pop());
+        assert_eq!(Some(999_998), c.pop());
+
+        h.clear();
+        assert!(h.is_empty());
+        assert_eq!(999_998, c.len());
+    }
+
+    #[test]
+    fn handle_get_pass() {
+        let mut h = BinomialHeap::new();
+        let a = h.push(1);
+        let b = h.push(2);
+
+        assert_eq!(Some(&1), h.get(a));
+        assert_eq!(Some(&2), h.get(b));
+        assert!(h.contains(a));
+
+        assert_eq!(Some(2), h.pop());
+        assert!(!h.contains(b));
+        assert_eq!(None, h.get(b));
+        assert_eq!(Some(&1), h.get(a));
+    }
+
+    #[test]
+    fn increase_key_pass() {
+        let mut h = BinomialHeap::new();
+        let handles: Vec<Handle> = (0..100).map(|x| h.push(x)).collect();
+
+        h.increase_key(handles[10], 1000);
+        h.increase_key(handles[20], 500);
+        assert_eq!(Some(&1000), h.get(handles[10]));
+
+        assert_eq!(Some(1000), h.pop());
+        assert_eq!(Some(500), h.pop());
+        assert_eq!(Some(99), h.pop());
+        assert_eq!(97, h.len());
+    }
+
+    #[test]
+    #[should_panic]
+    fn increase_key_smaller_fail() {
+        let mut h = BinomialHeap::new();
+        let a = h.push(10);
+        h.increase_key(a, 1);
+    }
+
+    #[test]
+    fn decrease_key_pass() {
+        let mut h = BinomialHeap::new();
+        let handles: Vec<Handle> = (0..100).map(|x| h.push(x)).collect();
+
+        h.decrease_key(handles[99], -1);
+        h.decrease_key(handles[98], -2);
+        assert_eq!(Some(&-1), h.get(handles[99]));
+        assert_eq!(100, h.len());
+
+        let xs = h.into_sorted_vec();
+        assert_eq!(97, xs[0]);
+        assert_eq!(&[-1, -2], &xs[98..]);
+    }
+
+    #[test]
+    fn remove_pass() {
+        let mut h = BinomialHeap::new();
+        let handles: Vec<Handle> = (0..100).map(|x| h.push(x)).collect();
+
+        for x in (0..100).step_by(3) {
+            assert_eq!(Some(x), h.remove(handles[x]));
+        }
+        assert_eq!(None, h.remove(handles[0]));
+
+        let xs = h.into_sorted_vec();
+        let expected: Vec<usize> = (0..100).rev().filter(|x| x % 3 != 0).collect();
+        assert_eq!(expected, xs);
+    }
+
+    #[test]
+    fn handles_random_pass() {
+        let mut seed = 23;
+        let mut h = BinomialHeap::new();
+        let mut live: Vec<(Handle, u64)> = Vec::new();
+
+        for _ in 0..5000 {
+            match lcg(&mut seed) % 5 {
+                0 | 1 => {
+                    let x = lcg(&mut seed) % 1000;
+                    live.push((h.push(x), x));
+                }
+                2 if !live.is_empty() => {
+                    let i = (lcg(&mut seed) as usize) % live.len();
+                    let (handle, x) = live.swap_remove(i);
+                    assert_eq!(Some(x), h.remove(handle));
+                }
+                3 if !live.is_empty() => {
+                    let i = (lcg(&mut seed) as usize) % live.len();
+                    let x = live[i].1 + lcg(&mut seed) % 100;
+                    h.increase_key(live[i].0, x);
+                    live[i].1 = x;
+                }
+                4 if !live.is_empty() => {
+                    let i = (lcg(&mut seed) as usize) % live.len();
+                    let x = live[i].1 / 2;
+                    h.decrease_key(live[i].0, x);
+                    live[i].1 = x;
+                }
+                _ => {
+                    let max = live.iter().map(|(_, x)| *x).max();
+                    assert_eq!(max, h.pop());
+
+                    // Any of the equal values could have been popped.
+                    if let Some(i) = live.iter().position(|(handle, _)| !h.contains(*handle)) {
+                        live.swap_remove(i);
+                    }
+                }
+            }
+
+            assert_eq!(live.len(), h.len());
+            assert_eq!(Ok(()), h.validate());
+            for (handle, x) in live.iter() {
+                assert_eq!(Some(x), h.get(*handle));
+            }
+        }
+    }
+
+    #[test]
+    fn validate_pass() {
+        let mut h: BinomialHeap<i32> = (0..100).collect();
+        assert_eq!(Ok(()), h.validate());
+
+        h.pop();
+        assert_eq!(Ok(()), h.validate());
+
+        let mut other: BinomialHeap<i32> = (0..37).collect();
+        h.append(&mut other);
+        assert_eq!(Ok(()), h.validate());
+        assert_eq!(Ok(()), other.validate());
+    }
+
+    #[test]
+    fn validate_order_fail() {
+        let mut h = BinomialHeap::new();
+        h.push(1);
+        let a = h.push(2);
+
+        // Break the order of the heap behind its back.
+        let x = h.node_of(a).unwrap();
+        let child = h.slot(x).child.unwrap();
+        h.slot_mut(child).item = 3;
+
+        let r = h.validate();
+        assert_eq!(
+            Err(InvariantError::Order {
+                parent: 0,
+                child: 1
+            }),
+            r
+        );
+
+        // The positions run through the roots in the order of the chain.
+        let mut h: BinomialHeap<i32> = (0..3).collect();
+        let root = h.root.and_then(|r| h.slot(r).sibling).unwrap();
+        let child = h.slot(root).child.unwrap();
+        h.slot_mut(child).item = 9;
+        assert_eq!(
+            Err(InvariantError::Order {
+                parent: 1,
+                child: 2
+            }),
+            h.validate()
+        );
+    }
+
+    #[test]
+    fn validate_length_fail() {
+        let mut h: BinomialHeap<i32> = (0..4).collect();
+        h.len = 5;
+
+        let r = h.validate();
+        assert_eq!(
+            Err(InvariantError::Length {
+                expected: 5,
+                found: 4
+            }),
+            r
+        );
+    }
+
+    #[test]
+    fn with_capacity_pass() {
+        let mut h = BinomialHeap::with_capacity(100);
+        let capacity = h.capacity();
+        assert!(capacity >= 100);
+
+        // Neither pushing nor popping reallocates the storage.
+        for x in 0..100 {
+            h.push(x);
+        }
+        for _ in 0..50 {
+            h.pop();
+        }
+        for x in 0..50 {
+            h.push(x);
+        }
+        assert_eq!(capacity, h.capacity());
+    }
+
+    #[test]
+    fn shrink_to_fit_pass() {
+        let mut h = BinomialHeap::new();
+        let handles: Vec<Handle> = (0..100).map(|x| h.push(x)).collect();
+        for _ in 0..90 {
+            h.pop();
+        }
+
+        h.shrink_to_fit();
+        assert!(h.capacity() < 100);
+        assert_eq!(10, h.len());
+
+        // The handles are still valid.
+        h.increase_key(handles[0], 200);
+        assert_eq!(Some(&200), h.get(handles[0]));
+        assert_eq!(Some(&9), h.get(handles[9]));
+        assert_eq!(Some(200), h.pop());
+        assert_eq!(Some(9), h.pop());
+    }
+
+    #[test]
+    fn clone_pass() {
+        let mut h = BinomialHeap::new();
+        h.push(1);
+        h.push(2);
+
+        let mut c = h.clone();
+        assert_eq!(Some(2), c.pop());
+        assert_eq!(2, h.len());
+        assert_eq!(1, c.len());
+    }
+}
@@ -1,4 +1,12 @@
 //! Binomial heap
+mod convert;
+mod iter;
+mod main;
 mod node;
+mod persistent;
 
+pub use crate::arena::Handle;
+pub use crate::binomial_heap::iter::*;
+pub use crate::binomial_heap::main::BinomialHeap;
 pub use crate::binomial_heap::node::*;
+pub use crate::binomial_heap::persistent::{PersistentBinomialHeap, PersistentIter};
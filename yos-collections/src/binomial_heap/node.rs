@@ -1,18 +1,28 @@
-use std::fmt::Debug;
-use std::mem;
+use crate::dot::Dot;
+use crate::invariant::InvariantError;
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::Debug;
+use core::mem::{self, ManuallyDrop};
+use core::ptr;
+
+/// An optional link to a boxed node.
+pub(crate) type Link<T> = Option<Box<Node<T>>>;
 
 /// The node for the binomial heap
-#[derive(Clone)]
 pub struct Node<T> {
     /// The stored value.
-    pub item: T,
+    pub(crate) item: T,
     /// The order of the node, we have 2^order elements
     /// in the tree with the root in this node.
-    pub order: usize,
+    pub(crate) order: usize,
     /// Optional sibling node
-    pub sibling: Option<Box<Node<T>>>,
+    pub(crate) sibling: Option<Box<Node<T>>>,
     /// Optional child node
-    pub child: Option<Box<Node<T>>>,
+    pub(crate) child: Option<Box<Node<T>>>,
 }
 
 impl<T> Node<T> {
@@ -35,10 +45,98 @@ impl<T> Node<T> {
     pub fn set_child(&mut self, child: Node<T>) {
         self.child = Some(Box::new(child))
     }
+
+    /// Returns the value stored in the node.
+    pub fn item(&self) -> &T {
+        &self.item
+    }
+
+    /// Returns the order of the node.
+    pub fn order(&self) -> usize {
+        self.order
+    }
+
+    /// Returns the sibling node, if any.
+    pub fn sibling(&self) -> Option<&Node<T>> {
+        self.sibling.as_deref()
+    }
+
+    /// Returns the child node, if any.
+    pub fn child(&self) -> Option<&Node<T>> {
+        self.child.as_deref()
+    }
+
+    /// Consumes the node and returns its item, its child and its sibling.
+    pub(crate) fn into_parts(mut self) -> (T, Link<T>, Link<T>) {
+        let child = self.child.take();
+        let sibling = self.sibling.take();
+
+        // The links have been taken out, so the only field which
+        // still owns anything is the item.
+        let node = ManuallyDrop::new(self);
+        let item = unsafe { ptr::read(&node.item) };
+
+        (item, child, sibling)
+    }
+
+    /// Drops a chain of nodes without recursion. The child and sibling links
+    /// form a binary tree which is rotated until the current node has no child,
+    /// at which point it can be dropped on its own.
+    fn drop_chain(mut current: Link<T>) {
+        while let Some(mut node) = current {
+            match node.child.take() {
+                Some(mut child) => {
+                    node.child = child.sibling.take();
+                    child.sibling = Some(node);
+                    current = Some(child);
+                }
+                None => current = node.sibling.take(),
+            }
+        }
+    }
+}
+
+impl<T> Drop for Node<T> {
+    fn drop(&mut self) {
+        Self::drop_chain(self.child.take());
+        Self::drop_chain(self.sibling.take());
+    }
+}
+
+impl<T: Clone> Clone for Node<T> {
+    /// Clones the node together with all its siblings and children
+    /// without recursion.
+    fn clone(&self) -> Self {
+        // Walk the nodes in pre-order (node, child, sibling) and
+        // remember for each of them which links are present.
+        let mut visited = Vec::new();
+        let mut stack = vec![self];
+        while let Some(node) = stack.pop() {
+            visited.push(node);
+            stack.extend(node.sibling());
+            stack.extend(node.child());
+        }
+
+        // Rebuild the nodes in reverse pre-order. When a node is rebuilt
+        // its child is on top of the stack, followed by its sibling.
+        let mut built: Vec<Node<T>> = Vec::new();
+        for node in visited.into_iter().rev() {
+            let mut copy = Node::with_order(node.item.clone(), node.order);
+            if node.child.is_some() {
+                copy.child = built.pop().map(Box::new);
+            }
+            if node.sibling.is_some() {
+                copy.sibling = built.pop().map(Box::new);
+            }
+            built.push(copy);
+        }
+
+        built.pop().unwrap()
+    }
 }
 
 impl<T: Debug> Debug for Node<T> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         let s = self
             .sibling
             .as_ref()
@@ -87,13 +185,7 @@ pub fn push<T: Ord>(root: &mut Option<Box<Node<T>>>, item: T) {
 /// Pops the maximum value form the heap.
 pub fn pop<T: Ord>(root: &mut Option<Box<Node<T>>>) -> Option<T> {
     remove_max(root).map(|max| {
-        let max = *max;
-        let Node {
-            item,
-            child,
-            order: _,
-            sibling: _,
-        } = max;
+        let (item, child, _) = (*max).into_parts();
 
         match *root {
             None => *root = child,
@@ -174,7 +266,7 @@ pub fn coalesce<T: Ord>(mut a: &mut Box<Node<T>>) {
                     || sibling
                         .sibling
                         .as_ref()
-                        .map_or(false, |c| c.order == sibling.order)
+                        .is_some_and(|c| c.order == sibling.order)
                 {
                     // Keep moving
 
@@ -250,6 +342,126 @@ pub fn remove_max<T: Ord>(mut a: &mut Option<Box<Node<T>>>) -> Option<Box<Node<T
     })
 }
 
+/// The place of a node in the forest, as seen while validating it.
+enum Place<'a, T> {
+    /// The node is a root, which follows a root of the given order.
+    Root(Option<usize>),
+    /// The node is a child of the node with the given position and value.
+    Child(usize, &'a T),
+}
+
+/// Validates a chain of nodes, checking that every child is smaller or equal
+/// than its parent, that the orders along the chain of roots are strictly
+/// increasing, and that a node of order `k` has `k` children of orders
+/// `0..k`, in ascending order. The nodes are named by their depth-first
+/// position, each node before its children and the children before the
+/// next sibling.
+pub fn validate<T: Ord>(root: &Option<Box<Node<T>>>) -> Result<(), InvariantError> {
+    let mut position = 0;
+    let mut stack: Vec<(&Node<T>, Place<'_, T>)> = root
+        .as_deref()
+        .map(|node| (node, Place::Root(None)))
+        .into_iter()
+        .collect();
+
+    while let Some((node, place)) = stack.pop() {
+        let id = position;
+        position += 1;
+
+        let next = match place {
+            Place::Root(previous) => {
+                if let Some(previous) = previous.filter(|previous| node.order <= *previous) {
+                    return Err(InvariantError::RootOrder {
+                        node: id,
+                        order: node.order,
+                        previous,
+                    });
+                }
+                Place::Root(Some(node.order))
+            }
+            Place::Child(parent, item) => {
+                if node.item > *item {
+                    return Err(InvariantError::Order { parent, child: id });
+                }
+                Place::Child(parent, item)
+            }
+        };
+
+        // The children must have the orders 0, 1, ... order - 1.
+        let mut children = 0;
+        let mut child = node.child();
+        while let Some(c) = child {
+            if children < node.order && c.order != children {
+                return Err(InvariantError::ChildOrder {
+                    node: id,
+                    expected: children,
+                    found: c.order,
+                });
+            }
+            children += 1;
+            child = c.sibling();
+        }
+
+        if children != node.order {
+            return Err(InvariantError::ChildCount {
+                node: id,
+                order: node.order,
+                children,
+            });
+        }
+
+        // The sibling is visited after all the children.
+        if let Some(sibling) = node.sibling() {
+            stack.push((sibling, next));
+        }
+        if let Some(child) = node.child() {
+            stack.push((child, Place::Child(id, &node.item)));
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns a chain of nodes in the Graphviz DOT language. The nodes are
+/// named by their depth-first position, as in `validate`, and labelled with
+/// their order and value. The solid edges go from a parent to its children
+/// and the dashed edges link the siblings, the roots included.
+pub fn to_dot<T: Debug>(root: &Option<Box<Node<T>>>) -> String {
+    let mut dot = Dot::new("BinomialHeap");
+    let mut roots = Vec::new();
+    let mut position = 0;
+    let mut stack: Vec<(&Node<T>, Option<usize>, Option<usize>)> = root
+        .as_deref()
+        .map(|node| (node, None, None))
+        .into_iter()
+        .collect();
+
+    while let Some((node, parent, previous)) = stack.pop() {
+        let id = position;
+        position += 1;
+        dot.node(id, Some(node.order), &node.item);
+
+        match parent {
+            Some(parent) => dot.child(parent, id),
+            None => roots.push(id),
+        }
+        if let Some(previous) = previous {
+            dot.sibling(previous, id);
+        }
+
+        // The sibling is visited after all the children.
+        if let Some(sibling) = node.sibling() {
+            stack.push((sibling, parent, Some(id)));
+        }
+        if let Some(child) = node.child() {
+            stack.push((child, Some(id), None));
+        }
+    }
+
+    dot.same_rank(&roots);
+    dot.finish()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -261,6 +473,204 @@ mod tests {
         assert!(!s.is_empty());
     }
 
+    /// Builds a chain of `len` siblings, with the values `0..len`.
+    fn sibling_chain(len: usize) -> Node<usize> {
+        let mut head = Node::with_order(0, 0);
+        for i in 1..len {
+            let mut node = Node::with_order(i, 0);
+            node.set_sibling(head);
+            head = node;
+        }
+        head
+    }
+
+    /// Builds a chain of `len` nodes linked through their children.
+    fn child_chain(len: usize) -> Node<usize> {
+        let mut head = Node::with_order(0, 0);
+        for i in 1..len {
+            let mut node = Node::with_order(i, 0);
+            node.set_child(head);
+            head = node;
+        }
+        head
+    }
+
+    #[test]
+    fn test_drop_long_sibling_chain() {
+        let n = sibling_chain(2_000_000);
+        drop(n);
+    }
+
+    #[test]
+    fn test_drop_long_child_chain() {
+        let n = child_chain(2_000_000);
+        drop(n);
+    }
+
+    #[test]
+    fn test_clone() {
+        let mut a = Node::with_order(20, 2);
+        let mut b = Node::with_order(10, 1);
+        b.set_sibling(Node::with_order(5, 0));
+        b.set_child(Node::with_order(7, 0));
+        a.set_child(b);
+        a.set_sibling(Node::with_order(30, 3));
+
+        let c = a.clone();
+        assert_eq!(20, c.item);
+        assert_eq!(2, c.order);
+        assert_eq!(30, *c.sibling().unwrap().item());
+        assert!(c.sibling().unwrap().sibling().is_none());
+
+        let cb = c.child().unwrap();
+        assert_eq!(10, *cb.item());
+        assert_eq!(1, cb.order());
+        assert_eq!(5, *cb.sibling().unwrap().item());
+        assert_eq!(7, *cb.child().unwrap().item());
+        assert!(cb.child().unwrap().sibling().is_none());
+    }
+
+    #[test]
+    fn test_clone_long_chains() {
+        let mut a = sibling_chain(1_000_000);
+        a.set_child(child_chain(1_000_000));
+
+        let c = a.clone();
+        assert_eq!(999_999, c.item);
+
+        let mut count = 0;
+        let mut sibling = Some(&c);
+        while let Some(node) = sibling {
+            count += 1;
+            sibling = node.sibling();
+        }
+        assert_eq!(1_000_000, count);
+
+        let mut count = 0;
+        let mut child = c.child();
+        while let Some(node) = child {
+            count += 1;
+            child = node.child();
+        }
+        assert_eq!(1_000_000, count);
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let mut root = None;
+        (0..3).for_each(|x| push(&mut root, x));
+
+        let expected = "digraph BinomialHeap {
+    node [shape=box];
+    n0 [label=\"#0 o0\\n2\"];
+    n1 [label=\"#1 o1\\n1\"];
+    n0 -> n1 [style=dashed, constraint=false];
+    n2 [label=\"#2 o0\\n0\"];
+    n1 -> n2;
+    { rank=same; n0; n1; }
+}
+";
+        assert_eq!(expected, to_dot(&root));
+        assert_eq!(
+            "digraph BinomialHeap {\n    node [shape=box];\n}\n",
+            to_dot::<u8>(&None)
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        let mut root = None;
+        (0..100).for_each(|x| push(&mut root, x));
+        assert_eq!(Ok(()), validate(&root));
+
+        pop(&mut root);
+        assert_eq!(Ok(()), validate(&root));
+
+        assert_eq!(Ok(()), validate::<i32>(&None));
+    }
+
+    #[test]
+    fn test_validate_order() {
+        let mut a = Node::with_order(10, 1);
+        a.set_child(Node::with_order(20, 0));
+
+        let r = validate(&Some(Box::new(a)));
+        assert_eq!(
+            Err(InvariantError::Order {
+                parent: 0,
+                child: 1
+            }),
+            r
+        );
+    }
+
+    #[test]
+    fn test_validate_root_order() {
+        let mut b = Node::with_order(20, 1);
+        b.set_child(Node::with_order(19, 0));
+
+        let mut a = Node::with_order(10, 1);
+        a.set_child(Node::with_order(9, 0));
+        a.set_sibling(b);
+
+        let r = validate(&Some(Box::new(a)));
+        assert_eq!(
+            Err(InvariantError::RootOrder {
+                node: 2,
+                order: 1,
+                previous: 1
+            }),
+            r
+        );
+    }
+
+    #[test]
+    fn test_validate_child_count() {
+        let a = Node::with_order(10, 1);
+
+        let r = validate(&Some(Box::new(a)));
+        assert_eq!(
+            Err(InvariantError::ChildCount {
+                node: 0,
+                order: 1,
+                children: 0
+            }),
+            r
+        );
+
+        let mut a = Node::with_order(10, 0);
+        a.set_child(Node::with_order(9, 0));
+
+        let r = validate(&Some(Box::new(a)));
+        assert_eq!(
+            Err(InvariantError::ChildCount {
+                node: 0,
+                order: 0,
+                children: 1
+            }),
+            r
+        );
+    }
+
+    #[test]
+    fn test_validate_child_order() {
+        let mut b = Node::with_order(8, 0);
+        b.set_sibling(Node::with_order(7, 0));
+
+        let mut a = Node::with_order(10, 2);
+        a.set_child(b);
+
+        let r = validate(&Some(Box::new(a)));
+        assert_eq!(
+            Err(InvariantError::ChildOrder {
+                node: 0,
+                expected: 1,
+                found: 0
+            }),
+            r
+        );
+    }
+
     #[test]
     fn test_peek_last() {
         // build the chain of siblings
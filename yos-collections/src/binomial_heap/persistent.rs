@@ -0,0 +1,617 @@
+//! Implementation of the persistent binomial heap
+use crate::invariant::InvariantError;
+
+use alloc::rc::Rc;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::Debug;
+use core::iter::FromIterator;
+use core::iter::FusedIterator;
+
+/// An optional link to a shared node.
+type Link<T> = Option<Rc<SharedNode<T>>>;
+
+/// A node to visit while validating, with the position and the value
+/// of its parent.
+type Visit<'a, T> = (&'a SharedNode<T>, Option<(usize, &'a T)>);
+
+/// A node of the persistent binomial heap. A node is never changed once it
+/// is shared, an operation copies the nodes whose links change and shares
+/// the rest with the older versions.
+struct SharedNode<T> {
+    /// The stored value.
+    item: T,
+    /// The order of the node, we have 2^order elements
+    /// in the tree with the root in this node.
+    order: usize,
+    /// Optional sibling node
+    sibling: Link<T>,
+    /// Optional child node, the children are kept in descending order.
+    child: Link<T>,
+}
+
+/// Implementation of a persistent binomial heap, a max-heap whose
+/// operations return a new version of the heap and leave the older
+/// versions valid.
+///
+/// The nodes are shared between the versions through `Rc`, so cloning a
+/// version is O(1) and an operation copies only the O(log n) nodes whose
+/// links change. The operations follow the ones of the binomial heap nodes:
+/// the chains of roots are merged in ascending order and the roots of the
+/// same order are coalesced, linking the smaller root under the greater
+/// one. The children are kept in descending order, so linking a tree
+/// shares all the children of the new parent.
+pub struct PersistentBinomialHeap<T> {
+    /// The first node in the chain of roots, in ascending order.
+    root: Link<T>,
+    len: usize,
+}
+
+impl<T> Clone for PersistentBinomialHeap<T> {
+    /// Returns a new version which shares all the nodes, in O(1).
+    fn clone(&self) -> Self {
+        Self {
+            root: self.root.clone(),
+            len: self.len,
+        }
+    }
+}
+
+impl<T> Default for PersistentBinomialHeap<T> {
+    fn default() -> Self {
+        Self { root: None, len: 0 }
+    }
+}
+
+impl<T: Debug> Debug for PersistentBinomialHeap<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: Ord + Clone> PersistentBinomialHeap<T> {
+    /// Creates a new, empty, instance of the persistent binomial heap.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::binomial_heap::PersistentBinomialHeap;
+    ///
+    /// let heap = PersistentBinomialHeap::<u8>::new();
+    /// assert!(heap.is_empty());
+    /// ```
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a new version of the heap with the value pushed into it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::binomial_heap::PersistentBinomialHeap;
+    ///
+    /// let a = PersistentBinomialHeap::new().push(1);
+    /// let b = a.push(5);
+    ///
+    /// assert_eq!(Some(&1), a.peek());
+    /// assert_eq!(Some(&5), b.peek());
+    /// ```
+    pub fn push(&self, item: T) -> Self {
+        let node = Rc::new(SharedNode {
+            item,
+            order: 0,
+            sibling: None,
+            child: None,
+        });
+
+        let roots = merge(vec![node], roots(&self.root));
+        Self {
+            root: chain(coalesce(roots)),
+            len: self.len + 1,
+        }
+    }
+
+    /// Returns the greatest value of the heap, together with a new version
+    /// of the heap without it, or `None` if the heap is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::binomial_heap::PersistentBinomialHeap;
+    ///
+    /// let a: PersistentBinomialHeap<_> = vec![3, 7, 5].into_iter().collect();
+    /// let (x, b) = a.pop().unwrap();
+    ///
+    /// assert_eq!(7, x);
+    /// assert_eq!(Some(&5), b.peek());
+    /// assert_eq!(Some(&7), a.peek());
+    /// ```
+    pub fn pop(&self) -> Option<(T, Self)> {
+        let mut rest = roots(&self.root);
+        let max = max_position(&rest)?;
+        let max = rest.remove(max);
+
+        // The children are in descending order, the roots in ascending order.
+        let mut children = roots(&max.child);
+        children.reverse();
+
+        let heap = Self {
+            root: chain(coalesce(merge(rest, children))),
+            len: self.len - 1,
+        };
+
+        Some((max.item.clone(), heap))
+    }
+
+    /// Returns a new version of the heap with the values of both heaps.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::binomial_heap::PersistentBinomialHeap;
+    ///
+    /// let a: PersistentBinomialHeap<_> = vec![1, 8].into_iter().collect();
+    /// let b: PersistentBinomialHeap<_> = vec![4, 2, 6].into_iter().collect();
+    /// let c = a.merge(&b);
+    ///
+    /// assert_eq!(5, c.len());
+    /// assert_eq!(2, a.len());
+    /// assert_eq!(vec![8, 6, 4, 2, 1], c.into_sorted_vec());
+    /// ```
+    pub fn merge(&self, other: &Self) -> Self {
+        let roots = merge(roots(&self.root), roots(&other.root));
+        Self {
+            root: chain(coalesce(roots)),
+            len: self.len + other.len,
+        }
+    }
+
+    /// Returns the greatest value of the heap, or `None` if it is empty.
+    pub fn peek(&self) -> Option<&T> {
+        siblings(&self.root)
+            .into_iter()
+            .fold(None, |max: Option<&T>, root| match max {
+                Some(max) if root.item <= *max => Some(max),
+                _ => Some(&root.item),
+            })
+    }
+
+    /// Returns the values of the heap in descending order,
+    /// the greatest value first.
+    pub fn into_sorted_vec(self) -> Vec<T> {
+        let mut heap = self;
+        let mut xs = Vec::with_capacity(heap.len());
+        while let Some((x, next)) = heap.pop() {
+            xs.push(x);
+            heap = next;
+        }
+        xs
+    }
+
+    /// Checks that every child is smaller or equal than its parent, that the
+    /// orders along the chain of roots are strictly increasing, and that a
+    /// node of order `k` has `k` children of orders `k - 1` down to `0`. The
+    /// nodes are named by their depth-first position, each node before its
+    /// children and the children before the next sibling.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::binomial_heap::PersistentBinomialHeap;
+    ///
+    /// let heap: PersistentBinomialHeap<_> = (0..10).collect();
+    /// assert!(heap.validate().is_ok());
+    /// ```
+    pub fn validate(&self) -> Result<(), InvariantError> {
+        let mut position = 0;
+        let mut found = 0;
+        let mut previous: Option<usize> = None;
+
+        let mut stack: Vec<Visit<'_, T>> = Vec::new();
+        for root in siblings(&self.root) {
+            if let Some(previous) = previous.filter(|previous| root.order <= *previous) {
+                return Err(InvariantError::RootOrder {
+                    node: position,
+                    order: root.order,
+                    previous,
+                });
+            }
+            previous = Some(root.order);
+
+            stack.push((root.as_ref(), None));
+            while let Some((node, parent)) = stack.pop() {
+                let id = position;
+                position += 1;
+                found += 1;
+
+                if let Some((parent, item)) = parent {
+                    if node.item > *item {
+                        return Err(InvariantError::Order { parent, child: id });
+                    }
+                }
+
+                // The children must have the orders order - 1, ... 0.
+                let children = siblings(&node.child);
+                for (i, child) in children.iter().enumerate() {
+                    let expected = node.order.wrapping_sub(i + 1);
+                    if i < node.order && child.order != expected {
+                        return Err(InvariantError::ChildOrder {
+                            node: id,
+                            expected,
+                            found: child.order,
+                        });
+                    }
+                }
+                if children.len() != node.order {
+                    return Err(InvariantError::ChildCount {
+                        node: id,
+                        order: node.order,
+                        children: children.len(),
+                    });
+                }
+
+                // Visit the children in their order.
+                for child in children.into_iter().rev() {
+                    stack.push((child, Some((id, &node.item))));
+                }
+            }
+        }
+
+        if found != self.len {
+            return Err(InvariantError::Length {
+                expected: self.len,
+                found,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+impl<T> PersistentBinomialHeap<T> {
+    /// Returns an iterator that is visiting all values in the heap,
+    /// in arbitrary order.
+    pub fn iter(&self) -> PersistentIter<'_, T> {
+        PersistentIter {
+            stack: self.root.as_deref().into_iter().collect(),
+            len: self.len,
+        }
+    }
+
+    /// Returns the number of elements in the heap.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns a flag indicating whenever the heap is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl<T: Ord + Clone> FromIterator<T> for PersistentBinomialHeap<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        iter.into_iter()
+            .fold(Self::new(), |heap, item| heap.push(item))
+    }
+}
+
+impl<'a, T> IntoIterator for &'a PersistentBinomialHeap<T> {
+    type Item = &'a T;
+    type IntoIter = PersistentIter<'a, T>;
+
+    fn into_iter(self) -> PersistentIter<'a, T> {
+        self.iter()
+    }
+}
+
+/// An iterator over the elements of a `PersistentBinomialHeap`,
+/// in arbitrary order.
+pub struct PersistentIter<'a, T: 'a> {
+    stack: Vec<&'a SharedNode<T>>,
+    len: usize,
+}
+
+impl<T> Clone for PersistentIter<'_, T> {
+    fn clone(&self) -> Self {
+        Self {
+            stack: self.stack.clone(),
+            len: self.len,
+        }
+    }
+}
+
+impl<T: Debug> Debug for PersistentIter<'_, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let items: Vec<&T> = self.clone().collect();
+        f.debug_tuple("PersistentIter").field(&items).finish()
+    }
+}
+
+impl<'a, T> Iterator for PersistentIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.stack.pop().map(|node| {
+            self.len -= 1;
+            self.stack.extend(node.sibling.as_deref());
+            self.stack.extend(node.child.as_deref());
+            &node.item
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> ExactSizeIterator for PersistentIter<'_, T> {}
+
+impl<T> FusedIterator for PersistentIter<'_, T> {}
+
+/// Returns the nodes of a chain of siblings.
+fn siblings<T>(link: &Link<T>) -> Vec<&Rc<SharedNode<T>>> {
+    let mut nodes = Vec::new();
+    let mut current = link.as_ref();
+    while let Some(node) = current {
+        nodes.push(node);
+        current = node.sibling.as_ref();
+    }
+    nodes
+}
+
+/// Returns the roots of a chain, without their sibling links.
+fn roots<T>(link: &Link<T>) -> Vec<Rc<SharedNode<T>>> {
+    siblings(link).into_iter().cloned().collect()
+}
+
+/// Returns the position of the root with the greatest value.
+fn max_position<T: Ord>(roots: &[Rc<SharedNode<T>>]) -> Option<usize> {
+    let mut max: Option<usize> = None;
+    for (i, root) in roots.iter().enumerate() {
+        let greater = match max {
+            Some(m) => root.item > roots[m].item,
+            None => true,
+        };
+        if greater {
+            max = Some(i);
+        }
+    }
+    max
+}
+
+/// Merges two chains of roots, each in ascending order. Of two roots with
+/// the same order, the root of `a` comes first.
+fn merge<T>(a: Vec<Rc<SharedNode<T>>>, b: Vec<Rc<SharedNode<T>>>) -> Vec<Rc<SharedNode<T>>> {
+    let mut merged = Vec::with_capacity(a.len() + b.len());
+    let mut a = a.into_iter().peekable();
+    let mut b = b.into_iter().peekable();
+
+    loop {
+        let take_a = match (a.peek(), b.peek()) {
+            (Some(x), Some(y)) => x.order <= y.order,
+            (Some(_), None) => true,
+            (None, Some(_)) => false,
+            (None, None) => return merged,
+        };
+
+        merged.extend(if take_a { a.next() } else { b.next() });
+    }
+}
+
+/// Links two trees of the same order, the tree with the smaller root goes
+/// under the other root as its first child.
+fn link<T: Clone>(a: &SharedNode<T>, b: &Rc<SharedNode<T>>) -> Rc<SharedNode<T>> {
+    debug_assert!(a.order == b.order);
+
+    let child = with_sibling(b, a.child.clone());
+    Rc::new(SharedNode {
+        item: a.item.clone(),
+        order: a.order + 1,
+        sibling: None,
+        child: Some(child),
+    })
+}
+
+/// Coalesces the roots of the same order, so the orders along the chain are
+/// strictly increasing. When three roots have the same order, the last two
+/// are coalesced.
+fn coalesce<T: Ord + Clone>(mut roots: Vec<Rc<SharedNode<T>>>) -> Vec<Rc<SharedNode<T>>> {
+    let mut i = 0;
+    while i + 1 < roots.len() {
+        let order = roots[i + 1].order;
+        if roots[i].order != order || roots.get(i + 2).is_some_and(|c| c.order == order) {
+            // Keep moving
+            i += 1;
+        } else if roots[i].item >= roots[i + 1].item {
+            // Coalesce under current
+            let sibling = roots.remove(i + 1);
+            roots[i] = link(&roots[i], &sibling);
+        } else {
+            // Coalesce under sibling
+            let current = roots.remove(i);
+            roots[i] = link(&roots[i], &current);
+        }
+    }
+
+    roots
+}
+
+/// Builds the chain of roots. The tail of the chain whose links do not
+/// change is shared with the older versions.
+fn chain<T: Clone>(roots: Vec<Rc<SharedNode<T>>>) -> Link<T> {
+    roots
+        .into_iter()
+        .rev()
+        .fold(None, |tail, node| Some(with_sibling(&node, tail)))
+}
+
+/// Returns the node with the given sibling, the node itself when it
+/// already links to that sibling, otherwise a copy.
+fn with_sibling<T: Clone>(node: &Rc<SharedNode<T>>, sibling: Link<T>) -> Rc<SharedNode<T>> {
+    let same = match (&node.sibling, &sibling) {
+        (Some(x), Some(y)) => Rc::ptr_eq(x, y),
+        (None, None) => true,
+        _ => false,
+    };
+
+    if same {
+        node.clone()
+    } else {
+        Rc::new(SharedNode {
+            item: node.item.clone(),
+            order: node.order,
+            sibling,
+            child: node.child.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::lcg;
+
+    #[test]
+    fn empty_pass() {
+        let h = PersistentBinomialHeap::<u8>::new();
+        assert!(h.is_empty());
+        assert_eq!(None, h.peek());
+        assert!(h.pop().is_none());
+        assert_eq!(Ok(()), h.validate());
+    }
+
+    #[test]
+    fn push_pop_pass() {
+        let mut seed = 8u64;
+        let xs: Vec<u64> = (0..500).map(|_| lcg(&mut seed) % 100).collect();
+
+        let mut h = PersistentBinomialHeap::new();
+        for x in xs.iter() {
+            h = h.push(*x);
+            assert_eq!(Ok(()), h.validate());
+        }
+
+        let mut ys = xs;
+        ys.sort_unstable_by(|a, b| b.cmp(a));
+        for y in ys {
+            let (x, next) = h.pop().unwrap();
+            assert_eq!(y, x);
+            assert_eq!(Ok(()), next.validate());
+            h = next;
+        }
+        assert!(h.is_empty());
+    }
+
+    #[test]
+    fn versions_pass() {
+        // Every version keeps its own values after the later operations.
+        let mut seed = 9u64;
+        let mut versions = vec![PersistentBinomialHeap::new()];
+        let mut models: Vec<Vec<u64>> = vec![vec![]];
+
+        for _ in 0..300 {
+            let v = (lcg(&mut seed) as usize) % versions.len();
+            let mut model = models[v].clone();
+
+            let next = if lcg(&mut seed) % 3 == 1 && !model.is_empty() {
+                model.sort_unstable();
+                let max = model.pop();
+                let (x, next) = versions[v].pop().unwrap();
+                assert_eq!(max, Some(x));
+                next
+            } else {
+                let x = lcg(&mut seed) % 50;
+                model.push(x);
+                versions[v].push(x)
+            };
+
+            versions.push(next);
+            models.push(model);
+        }
+
+        for (h, model) in versions.into_iter().zip(models) {
+            assert_eq!(Ok(()), h.validate());
+            let mut model = model;
+            model.sort_unstable_by(|a, b| b.cmp(a));
+            assert_eq!(model, h.into_sorted_vec());
+        }
+    }
+
+    #[test]
+    fn merge_pass() {
+        let a: PersistentBinomialHeap<_> = (0..37).collect();
+        let b: PersistentBinomialHeap<_> = (100..111).collect();
+
+        let c = a.merge(&b);
+        assert_eq!(Ok(()), c.validate());
+        assert_eq!(48, c.len());
+        assert_eq!(Some(&110), c.peek());
+
+        // The merged heaps did not change.
+        assert_eq!(Some(&36), a.peek());
+        assert_eq!((0..37).rev().collect::<Vec<_>>(), a.into_sorted_vec());
+        assert_eq!(11, b.len());
+    }
+
+    #[test]
+    fn sharing_pass() {
+        let a: PersistentBinomialHeap<_> = (0..64).collect();
+        let b = a.push(100);
+
+        // The single tree of order 6 is shared by both versions.
+        let tree = a.root.as_ref().unwrap();
+        let shared = b.root.as_ref().unwrap().sibling.as_ref().unwrap();
+        assert!(Rc::ptr_eq(tree, shared));
+    }
+
+    #[test]
+    fn iter_pass() {
+        let h: PersistentBinomialHeap<_> = (0..20).collect();
+        let mut xs: Vec<i32> = h.iter().cloned().collect();
+        xs.sort_unstable();
+        assert_eq!((0..20).collect::<Vec<_>>(), xs);
+        assert_eq!(20, (&h).into_iter().len());
+
+        let h = PersistentBinomialHeap::new().push(1);
+        assert_eq!("[1]", format!("{:?}", h));
+    }
+
+    #[test]
+    fn validate_fail() {
+        let h: PersistentBinomialHeap<_> = (0..4).collect();
+        let broken = PersistentBinomialHeap {
+            root: h.root.clone(),
+            len: 5,
+        };
+        assert_eq!(
+            Err(InvariantError::Length {
+                expected: 5,
+                found: 4
+            }),
+            broken.validate()
+        );
+
+        // A root of order 1 without children.
+        let node = Rc::new(SharedNode {
+            item: 1,
+            order: 1,
+            sibling: None,
+            child: None,
+        });
+        let broken = PersistentBinomialHeap {
+            root: Some(node),
+            len: 1,
+        };
+        assert_eq!(
+            Err(InvariantError::ChildCount {
+                node: 0,
+                order: 1,
+                children: 0
+            }),
+            broken.validate()
+        );
+    }
+}
@@ -0,0 +1,107 @@
+//! Strategies which define the order of the elements in a heap.
+use core::cmp::Ordering;
+
+/// A comparison strategy. The heaps keep at the top the element which
+/// is the greatest according to the strategy.
+pub trait Compare<T> {
+    /// Compares two elements.
+    fn compare(&self, a: &T, b: &T) -> Ordering;
+}
+
+/// Orders the elements by their `Ord` implementation, the heap is a max-heap.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MaxComparator;
+
+impl<T: Ord> Compare<T> for MaxComparator {
+    #[inline]
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        a.cmp(b)
+    }
+}
+
+/// Orders the elements by the reverse of their `Ord` implementation,
+/// the heap is a min-heap.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MinComparator;
+
+impl<T: Ord> Compare<T> for MinComparator {
+    #[inline]
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        b.cmp(a)
+    }
+}
+
+/// Orders the elements with a closure.
+#[derive(Clone, Copy, Debug)]
+pub struct FnComparator<F>(pub F);
+
+impl<T, F> Compare<T> for FnComparator<F>
+where
+    F: Fn(&T, &T) -> Ordering,
+{
+    #[inline]
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        (self.0)(a, b)
+    }
+}
+
+/// Orders the elements by a key extracted with a closure,
+/// the element with the greatest key is at the top of the heap.
+#[derive(Clone, Copy, Debug)]
+pub struct KeyComparator<F>(pub F);
+
+impl<T, K, F> Compare<T> for KeyComparator<F>
+where
+    K: Ord,
+    F: Fn(&T) -> K,
+{
+    #[inline]
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        (self.0)(a).cmp(&(self.0)(b))
+    }
+}
+
+/// Reverses the order of another comparator, the element which is the
+/// least according to the inner comparator is kept at the top.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ReverseComparator<C>(pub C);
+
+impl<T, C: Compare<T>> Compare<T> for ReverseComparator<C> {
+    #[inline]
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        self.0.compare(b, a)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn max_min_pass() {
+        assert_eq!(Ordering::Less, MaxComparator.compare(&1, &2));
+        assert_eq!(Ordering::Greater, MinComparator.compare(&1, &2));
+        assert_eq!(Ordering::Equal, MinComparator.compare(&2, &2));
+    }
+
+    #[test]
+    fn fn_key_pass() {
+        let c = FnComparator(|a: &i32, b: &i32| (a % 10).cmp(&(b % 10)));
+        assert_eq!(Ordering::Greater, c.compare(&9, &11));
+
+        let c = KeyComparator(|x: &(u8, char)| x.0);
+        assert_eq!(Ordering::Less, c.compare(&(1, 'z'), &(2, 'a')));
+    }
+
+    #[test]
+    fn reverse_pass() {
+        assert_eq!(
+            Ordering::Greater,
+            ReverseComparator(MaxComparator).compare(&1, &2)
+        );
+        assert_eq!(
+            Ordering::Less,
+            ReverseComparator(MinComparator).compare(&1, &2)
+        );
+    }
+}
@@ -0,0 +1,78 @@
+use super::DaryHeap;
+use crate::compare::{Compare, MaxComparator};
+use alloc::vec::Vec;
+use core::iter::FromIterator;
+
+impl<T: Ord, const D: usize> From<Vec<T>> for DaryHeap<T, D> {
+    /// Converts a `Vec<T>` into a `DaryHeap<T, D>`.
+    ///
+    /// This conversion happens in-place, and has `O(n)` time complexity.
+    fn from(vec: Vec<T>) -> Self {
+        let mut heap = Self::from_comparator(MaxComparator);
+        heap.data = vec;
+        heap.rebuild();
+        heap
+    }
+}
+
+impl<T, const D: usize, C> From<DaryHeap<T, D, C>> for Vec<T> {
+    fn from(heap: DaryHeap<T, D, C>) -> Vec<T> {
+        heap.data
+    }
+}
+
+impl<T: Ord, const D: usize> FromIterator<T> for DaryHeap<T, D> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> DaryHeap<T, D> {
+        DaryHeap::from(iter.into_iter().collect::<Vec<_>>())
+    }
+}
+
+impl<T, const D: usize, C: Compare<T>> Extend<T> for DaryHeap<T, D, C> {
+    /// Extends the d-ary heap with the elements of an iterator. When many
+    /// elements arrive at once the heap is rebuilt instead of pushing them
+    /// one by one.
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let start = self.data.len();
+        self.data.extend(iter);
+        self.rebuild_tail(start);
+    }
+}
+
+impl<'a, T: 'a + Copy, const D: usize, C: Compare<T>> Extend<&'a T> for DaryHeap<T, D, C> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().cloned());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_vec_to_vec() {
+        let mut h = DaryHeap::<_, 4>::from(vec![3, 1, 4]);
+        h.push(2);
+
+        let xs: Vec<i32> = h.into();
+        assert_eq!(xs.len(), 4);
+    }
+
+    #[test]
+    fn from_iterator() {
+        let h: DaryHeap<_, 4> = (0..100).collect();
+        assert_eq!(Ok(()), h.validate());
+        assert_eq!(Some(&99), h.peek());
+    }
+
+    #[test]
+    fn extend_pass() {
+        let mut h = DaryHeap::<_, 4>::from((0..100).collect::<Vec<_>>());
+        h.extend(&[200, -5]);
+        assert_eq!(Ok(()), h.validate());
+        assert_eq!(Some(&200), h.peek());
+
+        h.extend(1000..2000);
+        assert_eq!(Ok(()), h.validate());
+        assert_eq!(Some(&1999), h.peek());
+    }
+}
@@ -0,0 +1,84 @@
+//! Iterators over the elements in a d-ary heap.
+use super::DaryHeap;
+use crate::binary_heap::{IntoIter, Iter};
+use crate::compare::Compare;
+
+use core::fmt::Debug;
+use core::iter::FusedIterator;
+
+/// An owning iterator over the elements of a `DaryHeap`,
+/// which yields the elements in heap order.
+#[derive(Clone)]
+pub struct IntoIterSorted<T, const D: usize, C> {
+    heap: DaryHeap<T, D, C>,
+}
+
+impl<T, const D: usize, C> IntoIterSorted<T, D, C> {
+    /// Creates a new instance of the sorted into iterator.
+    pub fn new(heap: DaryHeap<T, D, C>) -> Self {
+        Self { heap }
+    }
+}
+
+impl<T: Debug, const D: usize, C> Debug for IntoIterSorted<T, D, C> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("IntoIterSorted")
+            .field(&self.heap.as_slice())
+            .finish()
+    }
+}
+
+impl<T, const D: usize, C: Compare<T>> Iterator for IntoIterSorted<T, D, C> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        self.heap.pop()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.heap.len();
+        (len, Some(len))
+    }
+}
+
+impl<T, const D: usize, C: Compare<T>> ExactSizeIterator for IntoIterSorted<T, D, C> {}
+
+impl<T, const D: usize, C: Compare<T>> FusedIterator for IntoIterSorted<T, D, C> {}
+
+impl<T, const D: usize, C> IntoIterator for DaryHeap<T, D, C> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter::new(self.data.into_iter())
+    }
+}
+
+impl<'a, T, const D: usize, C> IntoIterator for &'a DaryHeap<T, D, C> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn into_iter_pass() {
+        let h = DaryHeap::<_, 4>::from(vec![1, 2, 3]);
+
+        let mut xs: Vec<&i32> = (&h).into_iter().collect();
+        xs.sort_unstable();
+        assert_eq!(vec![&1, &2, &3], xs);
+
+        let mut xs: Vec<i32> = h.into_iter().collect();
+        xs.sort_unstable();
+        assert_eq!(vec![1, 2, 3], xs);
+    }
+}
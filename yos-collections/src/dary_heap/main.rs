@@ -0,0 +1,727 @@
+//! Implementation of the d-ary heap
+use super::iter::*;
+use super::peek_mut::PeekMut;
+use crate::binary_heap::{Drain, Iter};
+use crate::compare::{Compare, FnComparator, KeyComparator, MaxComparator, MinComparator};
+use crate::invariant::InvariantError;
+use yos_memcursor::MemCursor;
+
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt::Debug;
+use core::mem::swap;
+
+/// Implementation of the d-ary heap, a heap where every node has up to `D`
+/// children. A larger arity gives a shallower tree, which makes the pushes
+/// cheaper and keeps the children of a node close in memory.
+///
+/// The order of the elements is given by the comparator `C`. By default the
+/// heap is a max-heap, which keeps the greatest element at the top.
+pub struct DaryHeap<T, const D: usize, C = MaxComparator> {
+    pub(crate) data: Vec<T>,
+    pub(crate) cmp: C,
+}
+
+impl<T: Clone, const D: usize, C: Clone> Clone for DaryHeap<T, D, C> {
+    fn clone(&self) -> Self {
+        Self {
+            data: self.data.clone(),
+            cmp: self.cmp.clone(),
+        }
+    }
+
+    fn clone_from(&mut self, source: &Self) {
+        self.data.clone_from(&source.data);
+        self.cmp.clone_from(&source.cmp);
+    }
+}
+
+impl<T, const D: usize, C: Compare<T> + Default> Default for DaryHeap<T, D, C> {
+    fn default() -> Self {
+        Self::from_comparator(C::default())
+    }
+}
+
+impl<T: Debug, const D: usize, C> Debug for DaryHeap<T, D, C> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: Ord, const D: usize> DaryHeap<T, D> {
+    /// Creates a new instance of the d-ary heap.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::dary_heap::DaryHeap;
+    ///
+    /// let mut heap = DaryHeap::<_, 4>::new();
+    /// heap.push(1);
+    /// heap.push(3);
+    /// heap.push(2);
+    ///
+    /// assert_eq!(3, heap.len());
+    /// ```
+    pub fn new() -> Self {
+        Self::from_comparator(MaxComparator)
+    }
+
+    /// Creates a new instance of the d-ary heap with a given capacity.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::dary_heap::DaryHeap;
+    ///
+    /// let heap = DaryHeap::<u8, 4>::with_capacity(10);
+    /// assert!(heap.capacity() >= 10);
+    /// ```
+    pub fn with_capacity(capacity: usize) -> Self {
+        let _ = Self::ARITY;
+
+        Self {
+            data: Vec::<T>::with_capacity(capacity),
+            cmp: MaxComparator,
+        }
+    }
+}
+
+impl<T: Ord, const D: usize> DaryHeap<T, D, MinComparator> {
+    /// Creates a new instance of a min-heap, which keeps
+    /// the smallest element at the top.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::dary_heap::DaryHeap;
+    ///
+    /// let mut heap = DaryHeap::<_, 4, _>::new_min();
+    /// heap.push(3);
+    /// heap.push(1);
+    /// heap.push(2);
+    ///
+    /// assert_eq!(Some(1), heap.pop());
+    /// ```
+    pub fn new_min() -> Self {
+        Self::from_comparator(MinComparator)
+    }
+}
+
+impl<T, const D: usize, F> DaryHeap<T, D, FnComparator<F>>
+where
+    F: Fn(&T, &T) -> Ordering,
+{
+    /// Creates a new instance of the d-ary heap which orders the elements
+    /// with a closure. The greatest element, according to the closure,
+    /// is kept at the top.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::dary_heap::DaryHeap;
+    ///
+    /// let mut heap = DaryHeap::<_, 4, _>::with_comparator(|a: &i32, b: &i32| b.abs().cmp(&a.abs()));
+    /// heap.push(-3);
+    /// heap.push(1);
+    /// heap.push(-2);
+    ///
+    /// assert_eq!(Some(1), heap.pop());
+    /// assert_eq!(Some(-2), heap.pop());
+    /// ```
+    pub fn with_comparator(f: F) -> Self {
+        Self::from_comparator(FnComparator(f))
+    }
+}
+
+impl<T, const D: usize, K, F> DaryHeap<T, D, KeyComparator<F>>
+where
+    K: Ord,
+    F: Fn(&T) -> K,
+{
+    /// Creates a new instance of the d-ary heap which orders the elements
+    /// by a key extracted with a closure. The element with the greatest key
+    /// is kept at the top.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::dary_heap::DaryHeap;
+    ///
+    /// let mut heap = DaryHeap::<_, 4, _>::with_key(|x: &(&str, u32)| x.1);
+    /// heap.push(("low", 1));
+    /// heap.push(("high", 10));
+    ///
+    /// assert_eq!(Some(("high", 10)), heap.pop());
+    /// ```
+    pub fn with_key(f: F) -> Self {
+        Self::from_comparator(KeyComparator(f))
+    }
+}
+
+impl<T, const D: usize, C: Compare<T>> DaryHeap<T, D, C> {
+    /// The arity of the heap, checked when the heap is instantiated.
+    const ARITY: usize = {
+        assert!(D >= 2, "the arity of a d-ary heap must be at least 2");
+        D
+    };
+
+    /// Creates a new instance of the d-ary heap which orders
+    /// the elements with the given comparator.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::compare::MinComparator;
+    /// use yos_collections::dary_heap::DaryHeap;
+    ///
+    /// let mut heap = DaryHeap::<_, 8, _>::from_comparator(MinComparator);
+    /// heap.push(2);
+    /// heap.push(1);
+    ///
+    /// assert_eq!(Some(1), heap.pop());
+    /// ```
+    pub fn from_comparator(cmp: C) -> Self {
+        let _ = Self::ARITY;
+
+        Self {
+            data: Vec::<T>::new(),
+            cmp,
+        }
+    }
+
+    /// Returns a guard which gives mutable access to the greatest element,
+    /// or `None` if the d-ary heap is empty. The heap is fixed when the
+    /// guard is dropped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::dary_heap::DaryHeap;
+    ///
+    /// let mut heap = DaryHeap::<_, 4>::from(vec![1, 5, 3]);
+    /// if let Some(mut top) = heap.peek_mut() {
+    ///     *top = 2;
+    /// }
+    ///
+    /// assert_eq!(Some(&3), heap.peek());
+    /// ```
+    pub fn peek_mut(&mut self) -> Option<PeekMut<'_, T, D, C>> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(PeekMut::new(self))
+        }
+    }
+
+    /// Moves all the elements of `other` into `self`, leaving `other` empty.
+    ///
+    /// The elements of the smaller heap are pushed one by one into the larger
+    /// heap, unless rebuilding the whole heap is cheaper.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::dary_heap::DaryHeap;
+    ///
+    /// let mut a = DaryHeap::<_, 4>::from(vec![1, 5, 3]);
+    /// let mut b = DaryHeap::<_, 4>::from(vec![4, 2]);
+    /// a.append(&mut b);
+    ///
+    /// assert!(b.is_empty());
+    /// assert_eq!(vec![1, 2, 3, 4, 5], a.into_sorted_vec());
+    /// ```
+    pub fn append(&mut self, other: &mut Self) {
+        if self.len() < other.len() {
+            swap(self, other);
+        }
+
+        let start = self.data.len();
+        self.data.append(&mut other.data);
+        self.rebuild_tail(start);
+    }
+
+    /// Retains only the elements for which the predicate returns `true`.
+    /// The heap is rebuilt if any element was removed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::dary_heap::DaryHeap;
+    ///
+    /// let mut heap = DaryHeap::<_, 4>::from(vec![1, 2, 3, 4, 5, 6]);
+    /// heap.retain(|x| x % 2 == 0);
+    ///
+    /// assert_eq!(vec![2, 4, 6], heap.into_sorted_vec());
+    /// ```
+    pub fn retain<F>(&mut self, f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let len = self.len();
+        self.data.retain(f);
+        if self.len() < len {
+            self.rebuild();
+        }
+    }
+
+    /// Consumes the d-ary heap and returns a vector with the elements
+    /// in ascending priority by the comparator, the top of the heap last,
+    /// so a min-heap returns the values in descending order. The sorting
+    /// is done in place.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::dary_heap::DaryHeap;
+    ///
+    /// let heap = DaryHeap::<_, 4>::from(vec![4, 1, 5, 3, 2]);
+    /// assert_eq!(vec![1, 2, 3, 4, 5], heap.into_sorted_vec());
+    ///
+    /// let mut heap = DaryHeap::<_, 4, _>::new_min();
+    /// heap.extend(vec![4, 1, 5, 3, 2]);
+    /// assert_eq!(vec![5, 4, 3, 2, 1], heap.into_sorted_vec());
+    /// ```
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        let mut end = self.len();
+        while end > 1 {
+            end -= 1;
+
+            // Move the greatest element after the heap and sift down
+            // the element which took its place.
+            self.data.swap(0, end);
+            self.sift_down_range(0, end);
+        }
+
+        self.data
+    }
+
+    /// Consumes the d-ary heap and returns an iterator which
+    /// yields the elements in heap order, the greatest first.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::dary_heap::DaryHeap;
+    ///
+    /// let heap = DaryHeap::<_, 4>::from(vec![4, 1, 5, 3, 2]);
+    /// let xs: Vec<_> = heap.into_iter_sorted().take(2).collect();
+    ///
+    /// assert_eq!(vec![5, 4], xs);
+    /// ```
+    pub fn into_iter_sorted(self) -> IntoIterSorted<T, D, C> {
+        IntoIterSorted::new(self)
+    }
+
+    /// Removes the greatest item from the d-ary heap and returns it,
+    /// or `None` if it is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::dary_heap::DaryHeap;
+    ///
+    /// let mut heap = DaryHeap::<_, 4>::new();
+    /// assert_eq!(None, heap.pop());
+    ///
+    /// heap.push(1);
+    /// heap.push(3);
+    /// heap.push(2);
+    ///
+    /// assert_eq!(Some(3), heap.pop());
+    /// ```
+    pub fn pop(&mut self) -> Option<T> {
+        // Get the last element, a small one.
+        self.data.pop().map(|mut item| {
+            if !self.is_empty() {
+                // Swap the small element with the head of the heap
+                // and bubble it down to fix the heap.
+                swap(&mut item, &mut self.data[0]);
+                self.bubble_down(0);
+            }
+
+            item
+        })
+    }
+
+    /// Pushes a value into the d-ary heap.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::dary_heap::DaryHeap;
+    ///
+    /// let mut heap = DaryHeap::<_, 8>::new();
+    /// heap.push(1);
+    /// heap.push(3);
+    /// heap.push(2);
+    ///
+    /// assert_eq!(Some(&3), heap.peek());
+    /// ```
+    pub fn push(&mut self, value: T) {
+        let old_len = self.len();
+        self.data.push(value);
+
+        self.bubble_up(0, old_len);
+    }
+
+    /// Bubbles up into the heap the value at 'pos' up to the 'start' index.
+    fn bubble_up(&mut self, start: usize, pos: usize) {
+        unsafe {
+            // take out the value at 'pos' and create a hole.
+            let mut mc = MemCursor::new(&mut self.data, pos);
+
+            while mc.pos() > start {
+                let parent = (mc.pos() - 1) / Self::ARITY;
+
+                if self.cmp.compare(mc.element(), mc.get(parent)).is_le() {
+                    break;
+                }
+
+                mc.move_to(parent);
+            }
+        }
+    }
+
+    /// Bubbles down into the heap the value at 'pos'
+    pub(crate) fn bubble_down(&mut self, pos: usize) {
+        self.sift_down_range(pos, self.len());
+    }
+
+    /// Take an element at `pos` and move it down the heap,
+    /// while its children are larger.
+    fn sift_down_range(&mut self, pos: usize, end: usize) {
+        unsafe {
+            let mut mc = MemCursor::new(&mut self.data, pos);
+
+            let mut first = Self::ARITY * pos + 1;
+            while first < end {
+                // find the greatest of the children, the loop over a full
+                // group of children has a constant length and is unrolled.
+                let mut child = first;
+                if first + Self::ARITY <= end {
+                    for other in first + 1..first + Self::ARITY {
+                        if self.cmp.compare(mc.get(child), mc.get(other)).is_le() {
+                            child = other;
+                        }
+                    }
+                } else {
+                    for other in first + 1..end {
+                        if self.cmp.compare(mc.get(child), mc.get(other)).is_le() {
+                            child = other;
+                        }
+                    }
+                }
+
+                // if we are already in order, stop.
+                if self.cmp.compare(mc.get(child), mc.element()).is_lt() {
+                    break;
+                }
+
+                mc.move_to(child);
+                first = Self::ARITY * mc.pos() + 1;
+            }
+        }
+    }
+
+    /// Checks that every element of the d-ary heap is smaller or equal
+    /// than its parent. The error names the index of the first element
+    /// which is greater than its parent.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::dary_heap::DaryHeap;
+    ///
+    /// let heap = DaryHeap::<_, 4>::from(vec![1, 5, 2, 4, 3]);
+    /// assert!(heap.validate().is_ok());
+    /// ```
+    pub fn validate(&self) -> Result<(), InvariantError> {
+        for child in 1..self.len() {
+            let parent = (child - 1) / Self::ARITY;
+            if self
+                .cmp
+                .compare(&self.data[child], &self.data[parent])
+                .is_gt()
+            {
+                return Err(InvariantError::Order { parent, child });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Restores the heap after the elements starting at `start` were added
+    /// at the end of the data. Either the new elements are bubbled up one
+    /// by one or the whole heap is rebuilt, whichever is cheaper.
+    pub(crate) fn rebuild_tail(&mut self, start: usize) {
+        let len = self.len();
+        if start == len {
+            return;
+        }
+
+        let tail_len = len - start;
+
+        // Rebuilding costs about D * len comparisons, while bubbling up
+        // the new elements costs about tail_len * logD(start) comparisons.
+        let better_to_rebuild = if start < tail_len {
+            true
+        } else {
+            let log2_start = (usize::BITS - start.leading_zeros() - 1) as usize;
+            let log2_arity = (usize::BITS - Self::ARITY.leading_zeros() - 1) as usize;
+            Self::ARITY * len * log2_arity < tail_len * log2_start
+        };
+
+        if better_to_rebuild {
+            self.rebuild();
+        } else {
+            for pos in start..len {
+                self.bubble_up(0, pos);
+            }
+        }
+    }
+
+    /// Rebuilds the whole heap, from scratch.
+    pub(crate) fn rebuild(&mut self) {
+        if self.len() < 2 {
+            return;
+        }
+
+        // Sift down every node which has children, the last one first.
+        let mut n = (self.len() - 2) / Self::ARITY + 1;
+        while n > 0 {
+            n -= 1;
+            self.sift_down_range(n, self.len());
+        }
+    }
+}
+
+impl<T, const D: usize, C> DaryHeap<T, D, C> {
+    /// Returns an iterator that is visiting all values in the d-ary heap.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter::new(self.data.iter())
+    }
+
+    /// Returns the greatest element in the d-ary heap.
+    pub fn peek(&self) -> Option<&T> {
+        self.data.first()
+    }
+
+    /// Returns a slice with all the elements in the d-ary heap,
+    /// in the order they are stored.
+    pub fn as_slice(&self) -> &[T] {
+        self.data.as_slice()
+    }
+
+    /// Removes all the elements from the d-ary heap and returns them
+    /// in an arbitrary order. The allocated memory is kept.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::dary_heap::DaryHeap;
+    ///
+    /// let mut heap = DaryHeap::<_, 4>::from(vec![1, 3, 2]);
+    /// let mut xs: Vec<_> = heap.drain().collect();
+    /// xs.sort();
+    ///
+    /// assert_eq!(vec![1, 2, 3], xs);
+    /// assert!(heap.is_empty());
+    /// ```
+    pub fn drain(&mut self) -> Drain<'_, T> {
+        Drain::new(self.data.drain(..))
+    }
+
+    /// Removes all the elements from the d-ary heap.
+    pub fn clear(&mut self) {
+        self.data.clear();
+    }
+
+    /// Returns the number of elements the d-ary heap can hold.
+    pub fn capacity(&self) -> usize {
+        self.data.capacity()
+    }
+
+    /// Reserves the minimum capacity for additional more elements
+    /// to be inserted in the d-ary heap.
+    pub fn reserve_exact(&mut self, additional: usize) {
+        self.data.reserve_exact(additional);
+    }
+
+    /// Reserves capacity for at least additional more elements
+    /// to be inserted in the d-ary heap.
+    pub fn reserve(&mut self, additional: usize) {
+        self.data.reserve(additional);
+    }
+
+    /// Shrinks the allocated memory.
+    pub fn shrink_to_fit(&mut self) {
+        self.data.shrink_to_fit();
+    }
+
+    /// Returns the number of elements in the d-ary heap.
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns a flag indicating whenever the d-ary heap is empty.
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::lcg;
+
+    fn push_pop<const D: usize>() {
+        let mut seed = D as u64;
+        let xs: Vec<u64> = (0..1000).map(|_| lcg(&mut seed) % 500).collect();
+
+        let mut h = DaryHeap::<u64, D>::new();
+        for x in xs.iter() {
+            h.push(*x);
+        }
+        assert_eq!(Ok(()), h.validate());
+
+        let mut ys = xs;
+        ys.sort_unstable_by(|a, b| b.cmp(a));
+        let zs: Vec<u64> = std::iter::from_fn(|| h.pop()).collect();
+        assert_eq!(ys, zs);
+    }
+
+    #[test]
+    fn push_pop_pass() {
+        push_pop::<2>();
+        push_pop::<3>();
+        push_pop::<4>();
+        push_pop::<8>();
+        push_pop::<16>();
+    }
+
+    #[test]
+    fn new_pass() {
+        let h = DaryHeap::<u8, 4>::new();
+        assert_eq!(h.len(), 0);
+        assert!(h.is_empty());
+        assert_eq!(None, h.peek());
+    }
+
+    #[test]
+    fn default_pass() {
+        let h = DaryHeap::<u8, 4>::default();
+        assert!(h.is_empty());
+
+        let mut h = DaryHeap::<u8, 4, MinComparator>::default();
+        h.push(2);
+        h.push(1);
+        assert_eq!(Some(1), h.pop());
+    }
+
+    #[test]
+    fn debug_pass() {
+        let h = DaryHeap::<u8, 4>::from(vec![1]);
+        assert_eq!("[1]", format!("{:?}", h));
+    }
+
+    #[test]
+    fn rebuild_pass() {
+        for len in 0..50 {
+            let h = DaryHeap::<_, 3>::from((0..len).collect::<Vec<_>>());
+            assert_eq!(Ok(()), h.validate());
+            assert_eq!(len, h.len());
+        }
+    }
+
+    #[test]
+    fn validate_fail() {
+        let h = DaryHeap::<_, 4> {
+            data: vec![5, 4, 3, 2, 1, 6],
+            cmp: MaxComparator,
+        };
+        assert_eq!(
+            Err(InvariantError::Order {
+                parent: 1,
+                child: 5
+            }),
+            h.validate()
+        );
+    }
+
+    #[test]
+    fn new_min_pass() {
+        let mut h = DaryHeap::<_, 4, _>::new_min();
+        h.extend(vec![4, 2, 3, 5, 1]);
+        assert_eq!(Ok(()), h.validate());
+        assert_eq!(vec![5, 4, 3, 2, 1], h.into_sorted_vec());
+    }
+
+    #[test]
+    fn with_key_pass() {
+        let mut h = DaryHeap::<_, 4, _>::with_key(|x: &(u8, i32)| -x.1);
+        h.extend(vec![(1, 5), (2, -1), (3, 7)]);
+        assert_eq!(Some((2, -1)), h.pop());
+        assert_eq!(Some((1, 5)), h.pop());
+    }
+
+    #[test]
+    fn append_pass() {
+        let mut a = DaryHeap::<_, 4>::from((0..100).collect::<Vec<_>>());
+        let mut b = DaryHeap::<_, 4>::from(vec![150, 50, -1]);
+        a.append(&mut b);
+        assert!(b.is_empty());
+        assert_eq!(Ok(()), a.validate());
+
+        let mut b = DaryHeap::<_, 4>::from((0..1000).collect::<Vec<_>>());
+        a.append(&mut b);
+        assert_eq!(Ok(()), a.validate());
+        assert_eq!(1103, a.len());
+        assert_eq!(Some(&999), a.peek());
+    }
+
+    #[test]
+    fn retain_pass() {
+        let mut h = DaryHeap::<_, 8>::from((0..20).collect::<Vec<_>>());
+        h.retain(|x| x % 3 == 0);
+        assert_eq!(Ok(()), h.validate());
+        assert_eq!(vec![0, 3, 6, 9, 12, 15, 18], h.into_sorted_vec());
+    }
+
+    #[test]
+    fn drain_clear_pass() {
+        let mut h = DaryHeap::<_, 4>::from(vec![4, 2, 3]);
+        assert_eq!(3, h.drain().count());
+        assert!(h.is_empty());
+
+        h.push(1);
+        h.clear();
+        assert!(h.is_empty());
+    }
+
+    #[test]
+    fn into_sorted_vec_pass() {
+        let mut seed = 7u64;
+        let xs: Vec<u64> = (0..1000).map(|_| lcg(&mut seed) % 100).collect();
+        let h = DaryHeap::<_, 4>::from(xs.clone());
+
+        let mut ys = xs;
+        ys.sort_unstable();
+        assert_eq!(ys, h.into_sorted_vec());
+    }
+
+    #[test]
+    fn into_iter_sorted_pass() {
+        let h = DaryHeap::<_, 3>::from(vec![2, 5, 1, 4, 3]);
+        let it = h.into_iter_sorted();
+        assert_eq!(5, it.len());
+        assert_eq!(vec![5, 4, 3, 2, 1], it.collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn clone_pass() {
+        let h = DaryHeap::<_, 4>::from(vec![1, 2, 3]);
+        let c = h.clone();
+        assert_eq!(h.as_slice(), c.as_slice());
+    }
+}
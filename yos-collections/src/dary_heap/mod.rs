@@ -0,0 +1,10 @@
+//! D-ary heap
+mod convert;
+mod iter;
+mod main;
+mod peek_mut;
+
+pub use crate::binary_heap::{Drain, IntoIter, Iter};
+pub use crate::dary_heap::iter::IntoIterSorted;
+pub use crate::dary_heap::main::DaryHeap;
+pub use crate::dary_heap::peek_mut::PeekMut;
@@ -0,0 +1,103 @@
+//! A guard which gives mutable access to the greatest element of a d-ary heap.
+use super::DaryHeap;
+use crate::compare::Compare;
+
+use core::fmt::Debug;
+use core::ops::{Deref, DerefMut};
+
+/// A guard over the greatest element of a `DaryHeap`, returned by
+/// `DaryHeap::peek_mut`. When the guard is dropped the element is sifted
+/// down, if it was changed, so the heap is valid again.
+///
+/// The sifting goes through a `MemCursor`, which fills back the hole when a
+/// comparison panics, so no element is lost or dropped twice. If the guard is
+/// leaked after the element was changed, the heap order may be broken.
+pub struct PeekMut<'a, T: 'a, const D: usize, C: 'a + Compare<T>> {
+    heap: &'a mut DaryHeap<T, D, C>,
+    /// Whether the element was borrowed mutably and has to be sifted down.
+    sift: bool,
+}
+
+impl<'a, T, const D: usize, C: Compare<T>> PeekMut<'a, T, D, C> {
+    /// Creates a new guard over the greatest element of a non empty heap.
+    pub(crate) fn new(heap: &'a mut DaryHeap<T, D, C>) -> Self {
+        debug_assert!(!heap.is_empty());
+        Self { heap, sift: false }
+    }
+
+    /// Removes the greatest element from the d-ary heap and returns it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::dary_heap::{DaryHeap, PeekMut};
+    ///
+    /// let mut heap = DaryHeap::<_, 4>::from(vec![1, 3, 2]);
+    /// let top = heap.peek_mut().unwrap();
+    ///
+    /// assert_eq!(3, PeekMut::pop(top));
+    /// assert_eq!(Some(&2), heap.peek());
+    /// ```
+    pub fn pop(mut this: PeekMut<'a, T, D, C>) -> T {
+        // The pop re-establishes the heap, there is nothing to sift on drop.
+        this.sift = false;
+        this.heap.pop().expect("the heap is not empty")
+    }
+}
+
+impl<T, const D: usize, C: Compare<T>> Drop for PeekMut<'_, T, D, C> {
+    fn drop(&mut self) {
+        if self.sift {
+            self.heap.bubble_down(0);
+        }
+    }
+}
+
+impl<T, const D: usize, C: Compare<T>> Deref for PeekMut<'_, T, D, C> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.heap.data[0]
+    }
+}
+
+impl<T, const D: usize, C: Compare<T>> DerefMut for PeekMut<'_, T, D, C> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.sift = true;
+        &mut self.heap.data[0]
+    }
+}
+
+impl<T: Debug, const D: usize, C: Compare<T>> Debug for PeekMut<'_, T, D, C> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("PeekMut").field(&self.heap.data[0]).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dary_heap::{DaryHeap, PeekMut};
+
+    #[test]
+    fn peek_mut_pass() {
+        let mut h = DaryHeap::<_, 4>::from((0..20).collect::<Vec<_>>());
+        {
+            let mut top = h.peek_mut().unwrap();
+            assert_eq!(19, *top);
+            *top = 0;
+        }
+        assert_eq!(Ok(()), h.validate());
+        assert_eq!(Some(&18), h.peek());
+
+        let top = h.peek_mut().unwrap();
+        assert_eq!(18, PeekMut::pop(top));
+        assert_eq!(Ok(()), h.validate());
+        assert_eq!(19, h.len());
+    }
+
+    #[test]
+    fn peek_mut_empty_pass() {
+        let mut h = DaryHeap::<u8, 4>::new();
+        assert!(h.peek_mut().is_none());
+    }
+}
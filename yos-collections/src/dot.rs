@@ -0,0 +1,98 @@
+//! Writer for the Graphviz DOT language, used to render snapshots of the heaps.
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{Debug, Write};
+
+/// Builds a directed graph, node by node and edge by edge.
+pub(crate) struct Dot {
+    out: String,
+}
+
+impl Dot {
+    /// Starts a graph with the given name.
+    pub(crate) fn new(name: &str) -> Self {
+        let mut out = String::new();
+        writeln!(out, "digraph {} {{", name).unwrap();
+        writeln!(out, "    node [shape=box];").unwrap();
+        Self { out }
+    }
+
+    /// Adds the node `id`, labelled with the id, the optional order and the item.
+    pub(crate) fn node<T: Debug>(&mut self, id: usize, order: Option<usize>, item: &T) {
+        let item = escape(&format!("{:?}", item));
+        match order {
+            Some(order) => writeln!(
+                self.out,
+                "    n{} [label=\"#{} o{}\\n{}\"];",
+                id, id, order, item
+            ),
+            None => writeln!(self.out, "    n{} [label=\"#{}\\n{}\"];", id, id, item),
+        }
+        .unwrap();
+    }
+
+    /// Adds an edge from a parent to its child.
+    pub(crate) fn child(&mut self, from: usize, to: usize) {
+        writeln!(self.out, "    n{} -> n{};", from, to).unwrap();
+    }
+
+    /// Adds an edge from a node to its sibling. The sibling edges are dashed
+    /// and do not change the ranks, so the trees keep their shape.
+    pub(crate) fn sibling(&mut self, from: usize, to: usize) {
+        writeln!(
+            self.out,
+            "    n{} -> n{} [style=dashed, constraint=false];",
+            from, to
+        )
+        .unwrap();
+    }
+
+    /// Places the nodes on the same rank, used for the roots of a forest.
+    pub(crate) fn same_rank(&mut self, ids: &[usize]) {
+        if ids.len() > 1 {
+            let ids: Vec<String> = ids.iter().map(|id| format!("n{}", id)).collect();
+            writeln!(self.out, "    {{ rank=same; {}; }}", ids.join("; ")).unwrap();
+        }
+    }
+
+    /// Closes the graph and returns its text.
+    pub(crate) fn finish(mut self) -> String {
+        self.out.push_str("}\n");
+        self.out
+    }
+}
+
+/// Escapes a label for a quoted DOT string.
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn graph_pass() {
+        let mut dot = Dot::new("G");
+        dot.node(0, Some(1), &"a\"b");
+        dot.node(1, None, &2);
+        dot.child(0, 1);
+        dot.sibling(1, 0);
+        dot.same_rank(&[0]);
+        dot.same_rank(&[0, 1]);
+
+        let expected = "digraph G {
+    node [shape=box];
+    n0 [label=\"#0 o1\\n\\\"a\\\\\\\"b\\\"\"];
+    n1 [label=\"#1\\n2\"];
+    n0 -> n1;
+    n1 -> n0 [style=dashed, constraint=false];
+    { rank=same; n0; n1; }
+}
+";
+        assert_eq!(expected, dot.finish());
+    }
+}
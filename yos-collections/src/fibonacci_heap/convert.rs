@@ -0,0 +1,4 @@
+//! Conversions from and into a Fibonacci heap.
+use super::{FibonacciHeap, IntoIter, Iter};
+
+crate::handle_heap::conversions!(FibonacciHeap);
@@ -0,0 +1,4 @@
+//! Iterators over the elements in a Fibonacci heap.
+use super::main::Slot;
+
+crate::handle_heap::iterators!(FibonacciHeap, Slot);
@@ -0,0 +1,926 @@
+//! Implementation of the Fibonacci heap
+use super::iter::*;
+use crate::arena::Handle;
+use crate::compare::{Compare, FnComparator, KeyComparator, MaxComparator, MinComparator};
+use crate::invariant::InvariantError;
+use crate::pool::{Key, Pool, Rename};
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt::Debug;
+use core::mem;
+
+/// A node of the Fibonacci heap. The nodes are stored in a pool and are
+/// linked through their keys. The siblings form a circular list.
+#[derive(Clone)]
+pub(crate) struct Slot<T> {
+    /// The stored value.
+    pub(crate) item: T,
+    /// Optional parent node, the roots don't have a parent.
+    parent: Option<Key>,
+    /// Optional child node, any node of the circular list of children.
+    child: Option<Key>,
+    /// The previous node in the circular list of siblings.
+    left: Key,
+    /// The next node in the circular list of siblings.
+    right: Key,
+    /// The number of children.
+    degree: usize,
+    /// Whether the node lost a child since it became the child of its parent.
+    marked: bool,
+}
+
+impl<T> Slot<T> {
+    /// Replaces the links of a cloned node by the keys of the cloned nodes.
+    fn relink(&mut self, rename: &Rename) {
+        self.parent = rename.link(self.parent);
+        self.child = rename.link(self.child);
+        self.left = rename.key(self.left);
+        self.right = rename.key(self.right);
+    }
+}
+
+/// A list of siblings which is being visited by `validate`: its first node,
+/// the next node to visit, the parent and its position, and the number of
+/// nodes visited so far.
+type Visit = (Key, Key, Option<(Key, usize)>, usize);
+
+/// Implementation of the Fibonacci heap.
+///
+/// The push, the meld and the key updates which move an element towards
+/// the top take `O(1)` amortized time, the pop takes `O(log n)` amortized
+/// time. The order of the elements is given by the comparator `C`, and so
+/// is the direction of the key updates: `increase_key` moves an element
+/// towards the top in `O(1)`, `decrease_key` moves it away from the top in
+/// `O(log n)`. In a min-heap, created with `new_min`, the smaller values are
+/// closer to the top, so lowering a distance in Dijkstra's or Prim's
+/// algorithm is an `increase_key`. The `update_key` takes either direction.
+///
+/// The nodes live in arenas. A meld takes over the arenas of the other
+/// heap without moving its nodes, see `append`.
+pub struct FibonacciHeap<T, C = MaxComparator> {
+    /// The nodes of the heap.
+    pub(crate) nodes: Pool<Slot<T>>,
+    /// The root with the greatest value, in the circular list of roots.
+    top: Option<Key>,
+    cmp: C,
+}
+
+impl<T: Clone, C: Clone> Clone for FibonacciHeap<T, C> {
+    fn clone(&self) -> Self {
+        let (mut nodes, rename) = self.nodes.clone_renamed();
+        nodes.values_mut().for_each(|slot| slot.relink(&rename));
+
+        Self {
+            nodes,
+            top: rename.link(self.top),
+            cmp: self.cmp.clone(),
+        }
+    }
+}
+
+impl<T, C: Compare<T> + Default> Default for FibonacciHeap<T, C> {
+    fn default() -> Self {
+        Self::from_comparator(C::default())
+    }
+}
+
+impl<T: Debug, C> Debug for FibonacciHeap<T, C> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: Ord> FibonacciHeap<T> {
+    /// Creates a new instance of the Fibonacci heap.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::fibonacci_heap::FibonacciHeap;
+    ///
+    /// let mut heap = FibonacciHeap::new();
+    /// heap.push(1);
+    /// heap.push(3);
+    /// heap.push(2);
+    ///
+    /// assert_eq!(3, heap.len());
+    /// ```
+    pub fn new() -> Self {
+        Self::from_comparator(MaxComparator)
+    }
+
+    /// Creates a new instance of the Fibonacci heap with a given capacity.
+    /// Pushing up to `capacity` elements does not reallocate the storage.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            nodes: Pool::with_capacity(capacity),
+            top: None,
+            cmp: MaxComparator,
+        }
+    }
+}
+
+impl<T: Ord> FibonacciHeap<T, MinComparator> {
+    /// Creates a new instance of a min-heap, which keeps the smallest
+    /// element at the top. In a min-heap a smaller value moves the element
+    /// towards the top, so it is set by `increase_key` in `O(1)` amortized
+    /// time.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::fibonacci_heap::FibonacciHeap;
+    ///
+    /// let mut heap = FibonacciHeap::new_min();
+    /// heap.push(3);
+    /// let h = heap.push(5);
+    ///
+    /// heap.increase_key(h, 1);
+    /// assert_eq!(Some(1), heap.pop());
+    /// ```
+    pub fn new_min() -> Self {
+        Self::from_comparator(MinComparator)
+    }
+}
+
+impl<T, F> FibonacciHeap<T, FnComparator<F>>
+where
+    F: Fn(&T, &T) -> Ordering,
+{
+    /// Creates a new instance of the Fibonacci heap which orders the elements
+    /// with a closure. The greatest element, according to the closure,
+    /// is kept at the top.
+    pub fn with_comparator(f: F) -> Self {
+        Self::from_comparator(FnComparator(f))
+    }
+}
+
+impl<T, K, F> FibonacciHeap<T, KeyComparator<F>>
+where
+    K: Ord,
+    F: Fn(&T) -> K,
+{
+    /// Creates a new instance of the Fibonacci heap which orders the elements
+    /// by a key extracted with a closure. The element with the greatest key
+    /// is kept at the top.
+    pub fn with_key(f: F) -> Self {
+        Self::from_comparator(KeyComparator(f))
+    }
+}
+
+impl<T, C: Compare<T>> FibonacciHeap<T, C> {
+    /// Creates a new instance of the Fibonacci heap which orders
+    /// the elements with the given comparator.
+    pub fn from_comparator(cmp: C) -> Self {
+        Self {
+            nodes: Pool::new(),
+            top: None,
+            cmp,
+        }
+    }
+
+    /// Pushes a value into the Fibonacci heap and returns its handle.
+    /// The value is added as a new root in `O(1)` time.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::fibonacci_heap::FibonacciHeap;
+    ///
+    /// let mut heap = FibonacciHeap::new();
+    /// heap.push(1);
+    /// let h = heap.push(3);
+    ///
+    /// assert_eq!(Some(&3), heap.get(h));
+    /// assert_eq!(Some(&3), heap.peek());
+    /// ```
+    pub fn push(&mut self, item: T) -> Handle {
+        // The new node is a circular list on its own.
+        let x = self.nodes.vacant_key();
+        self.nodes.insert(Slot {
+            item,
+            parent: None,
+            child: None,
+            left: x,
+            right: x,
+            degree: 0,
+            marked: false,
+        });
+
+        self.add_root(x);
+        self.nodes.handle(x)
+    }
+
+    /// Removes the greatest item from the Fibonacci heap and returns it,
+    /// or `None` if it is empty. The trees left in the list of roots are
+    /// consolidated, so no two roots have the same degree.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::fibonacci_heap::FibonacciHeap;
+    ///
+    /// let mut heap = FibonacciHeap::new();
+    /// assert_eq!(None, heap.pop());
+    ///
+    /// heap.push(1);
+    /// heap.push(3);
+    /// heap.push(2);
+    ///
+    /// assert_eq!(Some(3), heap.pop());
+    /// assert_eq!(Some(2), heap.pop());
+    /// ```
+    pub fn pop(&mut self) -> Option<T> {
+        self.top?;
+
+        let x = self.detach_top();
+        Some(self.nodes.remove(x).item)
+    }
+
+    /// Moves all the elements of `other` into `self`, leaving `other` empty.
+    ///
+    /// The storage of `other` is taken over without moving its nodes and
+    /// the lists of roots are spliced in `O(1)` amortized time. The handles
+    /// of both heaps stay valid and name their elements in `self`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::fibonacci_heap::FibonacciHeap;
+    ///
+    /// let mut a = FibonacciHeap::new();
+    /// let g = a.push(1);
+    /// a.push(5);
+    /// let mut b = FibonacciHeap::new();
+    /// let h = b.push(3);
+    /// b.push(7);
+    /// b.push(2);
+    ///
+    /// a.append(&mut b);
+    ///
+    /// assert_eq!(5, a.len());
+    /// assert!(b.is_empty());
+    /// assert_eq!(Some(&7), a.peek());
+    /// assert_eq!(Some(&1), a.get(g));
+    /// assert_eq!(Some(&3), a.get(h));
+    /// ```
+    pub fn append(&mut self, other: &mut Self) {
+        self.nodes.append(&mut other.nodes);
+        if let Some(t) = other.top.take() {
+            self.add_root(t);
+        }
+    }
+
+    /// Replaces the value of the element with the given handle by a value
+    /// which the comparator orders greater or equal than the current one,
+    /// so the element moves towards the top. The node is cut from its
+    /// parent in `O(1)` amortized time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the handle is not in the heap or if the comparator orders
+    /// the new value below the current one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::fibonacci_heap::FibonacciHeap;
+    ///
+    /// let mut heap = FibonacciHeap::new();
+    /// heap.push(5);
+    /// let h = heap.push(1);
+    ///
+    /// heap.increase_key(h, 10);
+    /// assert_eq!(Some(&10), heap.peek());
+    /// ```
+    pub fn increase_key(&mut self, handle: Handle, item: T) {
+        let current = self.get(handle).expect("the handle is not in the heap");
+        assert!(
+            self.cmp.compare(&item, current).is_ge(),
+            "the new value is ordered below the current one"
+        );
+
+        self.update_key(handle, item);
+    }
+
+    /// Replaces the value of the element with the given handle by a value
+    /// which the comparator orders smaller or equal than the current one,
+    /// so the element moves away from the top. The node is taken out of the
+    /// heap and pushed back in `O(log n)` amortized time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the handle is not in the heap or if the comparator orders
+    /// the new value above the current one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::fibonacci_heap::FibonacciHeap;
+    ///
+    /// let mut heap = FibonacciHeap::new();
+    /// let h = heap.push(10);
+    /// heap.push(5);
+    ///
+    /// heap.decrease_key(h, 1);
+    /// assert_eq!(Some(&5), heap.peek());
+    /// assert_eq!(Some(&1), heap.get(h));
+    /// ```
+    pub fn decrease_key(&mut self, handle: Handle, item: T) {
+        let current = self.get(handle).expect("the handle is not in the heap");
+        assert!(
+            self.cmp.compare(&item, current).is_le(),
+            "the new value is ordered above the current one"
+        );
+
+        self.update_key(handle, item);
+    }
+
+    /// Replaces the value of the element with the given handle and returns
+    /// the old value. When the element moves towards the top it is cut from
+    /// its parent in `O(1)` amortized time, otherwise it is taken out of the
+    /// heap and pushed back in `O(log n)` amortized time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the handle is not in the heap.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::fibonacci_heap::FibonacciHeap;
+    ///
+    /// let mut heap = FibonacciHeap::with_key(|x: &(char, u32)| x.1);
+    /// heap.push(('a', 5));
+    /// let h = heap.push(('b', 1));
+    ///
+    /// assert_eq!(('b', 1), heap.update_key(h, ('b', 10)));
+    /// assert_eq!(Some(&('b', 10)), heap.peek());
+    /// ```
+    pub fn update_key(&mut self, handle: Handle, item: T) -> T {
+        let x = self
+            .nodes
+            .key(handle)
+            .expect("the handle is not in the heap");
+        let old = mem::replace(&mut self.slot_mut(x).item, item);
+
+        if self.cmp.compare(&self.slot(x).item, &old).is_ge() {
+            if let Some(p) = self.slot(x).parent {
+                if self.greater(x, p) {
+                    self.cut(x);
+                    self.cascading_cut(p);
+                }
+            }
+
+            if self.slot(x).parent.is_none() && self.greater(x, self.top.unwrap()) {
+                self.top = Some(x);
+            }
+        } else {
+            // The children may be greater than the new value,
+            // take the node out and add it back as a new root.
+            self.force_top(x);
+            let x = self.detach_top();
+            self.add_root(x);
+        }
+
+        old
+    }
+
+    /// Removes the element with the given handle from the Fibonacci heap
+    /// and returns it, or `None` if the handle is not in the heap.
+    /// The operation takes `O(log n)` amortized time.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::fibonacci_heap::FibonacciHeap;
+    ///
+    /// let mut heap = FibonacciHeap::new();
+    /// heap.push(1);
+    /// let h = heap.push(3);
+    /// heap.push(2);
+    ///
+    /// assert_eq!(Some(3), heap.remove(h));
+    /// assert_eq!(None, heap.remove(h));
+    /// assert_eq!(Some(2), heap.pop());
+    /// ```
+    pub fn remove(&mut self, handle: Handle) -> Option<T> {
+        let x = self.nodes.key(handle)?;
+        self.force_top(x);
+        let x = self.detach_top();
+        Some(self.nodes.remove(x).item)
+    }
+
+    /// Checks the invariants of the Fibonacci heap: every child is smaller
+    /// or equal than its parent and links back to it, the siblings link back
+    /// to each other, no root is greater than the top, the degree of a node
+    /// matches its number of children and every element is reachable from
+    /// the roots. The error names the nodes by their depth-first position,
+    /// the roots from the top along the list, each node before its children
+    /// and the children before the next sibling.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::fibonacci_heap::FibonacciHeap;
+    ///
+    /// let mut heap: FibonacciHeap<_> = vec![1, 5, 2, 4, 3].into_iter().collect();
+    /// heap.pop();
+    /// assert!(heap.validate().is_ok());
+    /// ```
+    pub fn validate(&self) -> Result<(), InvariantError> {
+        let mut position = 0;
+
+        let mut lists: Vec<Visit> = self.top.map(|t| (t, t, None, 0)).into_iter().collect();
+
+        while let Some((first, x, parent, count)) = lists.pop() {
+            let id = position;
+            position += 1;
+            if position > self.nodes.len() {
+                // A broken list may never come back to its first node.
+                return Err(InvariantError::Length {
+                    expected: self.nodes.len(),
+                    found: position,
+                });
+            }
+
+            let slot = self.slot(x);
+            if slot.parent != parent.map(|(p, _)| p) {
+                return Err(InvariantError::Parent { node: id });
+            }
+
+            match parent {
+                None if self.greater(x, first) => return Err(InvariantError::Top { node: id }),
+                Some((p, parent)) if self.greater(x, p) => {
+                    return Err(InvariantError::Order { parent, child: id })
+                }
+                _ => {}
+            }
+
+            if self.nodes.get(slot.right).map(|s| s.left) != Some(x) {
+                return Err(InvariantError::Sibling { node: id });
+            }
+
+            // The next sibling comes after the children of the node.
+            if slot.right != first {
+                lists.push((first, slot.right, parent, count + 1));
+            } else if let Some((p, parent)) = parent {
+                let degree = self.slot(p).degree;
+                if count + 1 != degree {
+                    return Err(InvariantError::ChildCount {
+                        node: parent,
+                        order: degree,
+                        children: count + 1,
+                    });
+                }
+            }
+
+            match slot.child {
+                Some(c) => lists.push((c, c, Some((x, id)), 0)),
+                None if slot.degree != 0 => {
+                    return Err(InvariantError::ChildCount {
+                        node: id,
+                        order: slot.degree,
+                        children: 0,
+                    })
+                }
+                None => {}
+            }
+        }
+
+        if position != self.nodes.len() {
+            return Err(InvariantError::Length {
+                expected: self.nodes.len(),
+                found: position,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Returns true if the value at `a` is greater than the value at `b`.
+    fn greater(&self, a: Key, b: Key) -> bool {
+        self.cmp
+            .compare(&self.slot(a).item, &self.slot(b).item)
+            .is_gt()
+    }
+
+    /// Adds the circular list which contains `x` to the list of roots,
+    /// and updates the top if `x` is greater than it.
+    fn add_root(&mut self, x: Key) {
+        match self.top {
+            None => self.top = Some(x),
+            Some(t) => {
+                self.splice(t, x);
+                if self.greater(x, t) {
+                    self.top = Some(x);
+                }
+            }
+        }
+    }
+
+    /// Cuts the node from its tree and makes it the top, no matter its value.
+    fn force_top(&mut self, x: Key) {
+        if let Some(p) = self.slot(x).parent {
+            self.cut(x);
+            self.cascading_cut(p);
+        }
+
+        self.top = Some(x);
+    }
+
+    /// Takes the top out of the list of roots, moves its children into the
+    /// list of roots and consolidates the roots. The node stays in the
+    /// arena as a single root which is not linked to the heap.
+    fn detach_top(&mut self) -> Key {
+        let x = self.top.expect("the heap is not empty");
+
+        // The children become roots.
+        if let Some(c) = self.slot(x).child {
+            let mut y = c;
+            loop {
+                let slot = self.slot_mut(y);
+                slot.parent = None;
+                slot.marked = false;
+                y = slot.right;
+                if y == c {
+                    break;
+                }
+            }
+
+            self.splice(x, c);
+            let slot = self.slot_mut(x);
+            slot.child = None;
+            slot.degree = 0;
+        }
+
+        let right = self.slot(x).right;
+        if right == x {
+            self.top = None;
+        } else {
+            self.unlink(x);
+            self.top = Some(right);
+            self.consolidate();
+        }
+
+        x
+    }
+
+    /// Links the roots with the same degree until all the roots
+    /// have different degrees, then finds the new top.
+    fn consolidate(&mut self) {
+        let top = self.top.expect("the heap is not empty");
+
+        let mut roots = vec![top];
+        let mut current = self.slot(top).right;
+        while current != top {
+            roots.push(current);
+            current = self.slot(current).right;
+        }
+
+        // The root of each degree found so far.
+        let mut degrees: Vec<Option<Key>> = Vec::new();
+        for mut x in roots {
+            let mut d = self.slot(x).degree;
+            loop {
+                if d >= degrees.len() {
+                    degrees.resize(d + 1, None);
+                }
+
+                match degrees[d].take() {
+                    None => break,
+                    Some(mut y) => {
+                        if self.greater(y, x) {
+                            mem::swap(&mut x, &mut y);
+                        }
+                        self.link(y, x);
+                        d += 1;
+                    }
+                }
+            }
+            degrees[d] = Some(x);
+        }
+
+        // Rebuild the list of roots.
+        self.top = None;
+        for x in degrees.into_iter().flatten() {
+            self.singleton(x);
+            self.add_root(x);
+        }
+    }
+
+    /// Makes the root `y` a child of the root `x`.
+    fn link(&mut self, y: Key, x: Key) {
+        self.singleton(y);
+        let slot = self.slot_mut(y);
+        slot.parent = Some(x);
+        slot.marked = false;
+
+        match self.slot(x).child {
+            None => self.slot_mut(x).child = Some(y),
+            Some(c) => self.splice(c, y),
+        }
+        self.slot_mut(x).degree += 1;
+    }
+
+    /// Cuts the node from its parent and adds it to the list of roots.
+    fn cut(&mut self, x: Key) {
+        let p = self.slot(x).parent.expect("the node has a parent");
+
+        let right = self.slot(x).right;
+        if right == x {
+            self.slot_mut(p).child = None;
+        } else {
+            if self.slot(p).child == Some(x) {
+                self.slot_mut(p).child = Some(right);
+            }
+            self.unlink(x);
+        }
+        self.slot_mut(p).degree -= 1;
+
+        let slot = self.slot_mut(x);
+        slot.parent = None;
+        slot.marked = false;
+        self.add_root(x);
+    }
+
+    /// Walks up from `y`, cutting the marked nodes, until it finds
+    /// a node which is not marked and marks it.
+    fn cascading_cut(&mut self, mut y: Key) {
+        while let Some(z) = self.slot(y).parent {
+            if !self.slot(y).marked {
+                self.slot_mut(y).marked = true;
+                break;
+            }
+
+            self.cut(y);
+            y = z;
+        }
+    }
+}
+
+impl<T, C> FibonacciHeap<T, C> {
+    /// Returns the greatest element in the Fibonacci heap,
+    /// or `None` if it is empty.
+    pub fn peek(&self) -> Option<&T> {
+        self.top.map(|x| &self.slot(x).item)
+    }
+
+    /// Returns an iterator that is visiting all values in the Fibonacci heap,
+    /// in arbitrary order.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter::new(self.nodes.iter(), self.nodes.len())
+    }
+
+    /// Returns the value of the element with the given handle,
+    /// or `None` if the handle is not in the heap.
+    pub fn get(&self, handle: Handle) -> Option<&T> {
+        self.nodes.key(handle).map(|x| &self.slot(x).item)
+    }
+
+    /// Returns a flag indicating whenever the handle is in the heap.
+    pub fn contains(&self, handle: Handle) -> bool {
+        self.nodes.key(handle).is_some()
+    }
+
+    /// Returns the number of elements in the Fibonacci heap.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns a flag indicating whenever the Fibonacci heap is empty.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.len() == 0
+    }
+
+    /// Drops all the elements from the Fibonacci heap.
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+        self.top = None;
+    }
+
+    /// Returns the number of elements the Fibonacci heap can hold
+    /// without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.nodes.capacity()
+    }
+
+    /// Reserves capacity for at least `additional` more elements
+    /// to be inserted in the Fibonacci heap.
+    pub fn reserve(&mut self, additional: usize) {
+        self.nodes.reserve(additional);
+    }
+
+    fn slot(&self, x: Key) -> &Slot<T> {
+        self.nodes.get(x).expect("the node is vacant")
+    }
+
+    fn slot_mut(&mut self, x: Key) -> &mut Slot<T> {
+        self.nodes.get_mut(x).expect("the node is vacant")
+    }
+
+    /// Makes `x` a circular list with a single node.
+    fn singleton(&mut self, x: Key) {
+        let slot = self.slot_mut(x);
+        slot.left = x;
+        slot.right = x;
+    }
+
+    /// Joins the circular list which contains `a` with the one which contains `b`.
+    fn splice(&mut self, a: Key, b: Key) {
+        let a_right = self.slot(a).right;
+        let b_left = self.slot(b).left;
+
+        self.slot_mut(a).right = b;
+        self.slot_mut(b).left = a;
+        self.slot_mut(b_left).right = a_right;
+        self.slot_mut(a_right).left = b_left;
+    }
+
+    /// Takes `x` out of its circular list.
+    fn unlink(&mut self, x: Key) {
+        let left = self.slot(x).left;
+        let right = self.slot(x).right;
+
+        self.slot_mut(left).right = right;
+        self.slot_mut(right).left = left;
+        self.singleton(x);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::lcg;
+
+    #[test]
+    fn new_pass() {
+        let h = FibonacciHeap::<u8>::new();
+        assert!(h.is_empty());
+        assert_eq!(None, h.peek());
+        assert_eq!(Ok(()), h.validate());
+    }
+
+    #[test]
+    fn default_pass() {
+        let mut h = FibonacciHeap::<u8, MinComparator>::default();
+        h.push(2);
+        h.push(1);
+        assert_eq!(Some(1), h.pop());
+    }
+
+    #[test]
+    fn debug_pass() {
+        let mut h = FibonacciHeap::new();
+        h.push(1);
+        assert_eq!("[1]", format!("{:?}", h));
+    }
+
+    #[test]
+    fn push_pop_pass() {
+        let mut seed = 3u64;
+        let xs: Vec<u64> = (0..1000).map(|_| lcg(&mut seed) % 300).collect();
+
+        let mut h = FibonacciHeap::new();
+        for x in xs.iter() {
+            h.push(*x);
+        }
+        assert_eq!(Ok(()), h.validate());
+
+        let mut ys = xs;
+        ys.sort_unstable_by(|a, b| b.cmp(a));
+        for y in ys {
+            assert_eq!(Some(y), h.pop());
+            assert_eq!(Ok(()), h.validate());
+        }
+        assert_eq!(None, h.pop());
+    }
+
+    #[test]
+    fn new_min_pass() {
+        let mut h = FibonacciHeap::new_min();
+        for x in [4, 2, 3, 5, 1].iter() {
+            h.push(*x);
+        }
+
+        for x in 1..6 {
+            assert_eq!(Some(x), h.pop());
+        }
+    }
+
+    #[test]
+    fn with_comparator_pass() {
+        let mut h = FibonacciHeap::with_comparator(|a: &i32, b: &i32| b.abs().cmp(&a.abs()));
+        h.push(-3);
+        h.push(1);
+        h.push(-2);
+        assert_eq!(Some(1), h.pop());
+        assert_eq!(Some(-2), h.pop());
+    }
+
+    #[test]
+    fn keys_pass() {
+        let mut h = FibonacciHeap::new_min();
+        let hs: Vec<Handle> = (0..20).map(|x| h.push(x + 100)).collect();
+        h.pop();
+        assert_eq!(Ok(()), h.validate());
+
+        // Moving towards the top cuts the node.
+        h.increase_key(hs[10], 5);
+        assert_eq!(Ok(()), h.validate());
+        assert_eq!(Some(&5), h.peek());
+
+        // Moving away from the top takes the node out.
+        h.decrease_key(hs[10], 200);
+        assert_eq!(Ok(()), h.validate());
+        assert_eq!(Some(&101), h.peek());
+        assert_eq!(Some(&200), h.get(hs[10]));
+
+        assert_eq!(Some(115), h.remove(hs[15]));
+        assert!(!h.contains(hs[15]));
+        assert_eq!(Ok(()), h.validate());
+        assert_eq!(18, h.len());
+    }
+
+    #[test]
+    fn keys_comparator_pass() {
+        // The direction of the update is given by the comparator, which
+        // disagrees here with the order of the tuples.
+        let mut h = FibonacciHeap::with_key(|x: &(u32, u32)| x.1);
+        h.push((0, 5));
+        let a = h.push((5, 1));
+        h.increase_key(a, (1, 10));
+        assert_eq!(Some(&(1, 10)), h.peek());
+        h.decrease_key(a, (9, 0));
+        assert_eq!(Some(&(0, 5)), h.peek());
+
+        // The elements don't have to implement `Ord`.
+        let mut h = FibonacciHeap::with_comparator(|a: &f64, b: &f64| a.total_cmp(b));
+        h.push(2.5);
+        let b = h.push(0.5);
+        h.increase_key(b, 4.0);
+        assert_eq!(Some(4.0), h.pop());
+        assert_eq!(Ok(()), h.validate());
+    }
+
+    #[test]
+    #[should_panic]
+    fn increase_key_fail() {
+        let mut h = FibonacciHeap::new();
+        let x = h.push(10);
+        h.increase_key(x, 1);
+    }
+
+    #[test]
+    fn append_pass() {
+        let mut a: FibonacciHeap<i32> = (0..10).collect();
+        let mut b: FibonacciHeap<i32> = (10..17).collect();
+        a.pop();
+        b.pop();
+
+        a.append(&mut b);
+        assert!(b.is_empty());
+        assert_eq!(15, a.len());
+        assert_eq!(Ok(()), a.validate());
+
+        let xs: Vec<i32> = std::iter::from_fn(|| a.pop()).collect();
+        let mut ys: Vec<i32> = (0..9).chain(10..16).collect();
+        ys.reverse();
+        assert_eq!(ys, xs);
+    }
+
+    #[test]
+    fn validate_fail() {
+        let mut h: FibonacciHeap<i32> = (0..4).collect();
+        h.pop();
+
+        // The node is the only child of its parent, so it follows its parent.
+        let child = h.nodes.iter().find(|(_, s)| s.parent.is_some()).unwrap().0;
+        h.nodes.get_mut(child).unwrap().item = 100;
+        match h.validate() {
+            Err(InvariantError::Order { parent, child }) => assert_eq!(parent + 1, child),
+            r => panic!("unexpected result {:?}", r),
+        }
+    }
+
+    #[test]
+    fn clone_pass() {
+        let mut h: FibonacciHeap<i32> = (0..10).collect();
+        h.pop();
+
+        let mut c = h.clone();
+        assert_eq!(Ok(()), c.validate());
+        assert_eq!(h.pop(), c.pop());
+        assert_eq!(h.len(), c.len());
+    }
+
+    #[test]
+    fn clear_pass() {
+        let mut h: FibonacciHeap<i32> = (0..10).collect();
+        h.clear();
+        assert!(h.is_empty());
+        assert_eq!(None, h.pop());
+    }
+}
@@ -0,0 +1,8 @@
+//! Fibonacci heap
+mod convert;
+mod iter;
+mod main;
+
+pub use crate::arena::Handle;
+pub use crate::fibonacci_heap::iter::*;
+pub use crate::fibonacci_heap::main::FibonacciHeap;
@@ -0,0 +1,344 @@
+//! Code shared by the heaps which keep their nodes in a pool and give out
+//! handles to their elements: the binomial, Fibonacci and pairing heaps.
+
+/// Defines the `Iter` and `IntoIter` iterators over the elements of a heap.
+/// The iterators walk the slots of the pool of the heap, the slot type is
+/// given by its name in the calling module and keeps the element in its
+/// `item` field.
+macro_rules! iterators {
+    ($heap:ident, $slot:ident) => {
+        #[doc = concat!("An iterator over the elements of a `", stringify!($heap), "`,")]
+        /// in arbitrary order.
+        pub struct Iter<'a, T: 'a> {
+            iter: $crate::pool::Iter<'a, $slot<T>>,
+            len: usize,
+        }
+
+        impl<'a, T> Iter<'a, T> {
+            /// Creates a new instance of the iterator over the nodes of the heap.
+            pub(crate) fn new(iter: $crate::pool::Iter<'a, $slot<T>>, len: usize) -> Self {
+                Self { iter, len }
+            }
+        }
+
+        impl<T: core::fmt::Debug> core::fmt::Debug for Iter<'_, T> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                let items: alloc::vec::Vec<&T> = self.clone().collect();
+                f.debug_tuple("Iter").field(&items).finish()
+            }
+        }
+
+        impl<T> Clone for Iter<'_, T> {
+            fn clone(&self) -> Self {
+                Iter {
+                    iter: self.iter.clone(),
+                    len: self.len,
+                }
+            }
+        }
+
+        impl<'a, T> Iterator for Iter<'a, T> {
+            type Item = &'a T;
+
+            #[inline]
+            fn next(&mut self) -> Option<&'a T> {
+                if self.len == 0 {
+                    return None;
+                }
+
+                self.iter.next().map(|(_, slot)| {
+                    self.len -= 1;
+                    &slot.item
+                })
+            }
+
+            #[inline]
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                (self.len, Some(self.len))
+            }
+        }
+
+        impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+        impl<T> core::iter::FusedIterator for Iter<'_, T> {}
+
+        #[doc = concat!("An owning iterator over the elements of a `", stringify!($heap), "`,")]
+        /// in arbitrary order.
+        pub struct IntoIter<T> {
+            iter: $crate::pool::IntoIter<$slot<T>>,
+            len: usize,
+        }
+
+        impl<T> IntoIter<T> {
+            /// Creates a new instance of the into iterator, taking ownership of the nodes.
+            pub(crate) fn new(iter: $crate::pool::IntoIter<$slot<T>>, len: usize) -> Self {
+                Self { iter, len }
+            }
+        }
+
+        impl<T: core::fmt::Debug> core::fmt::Debug for IntoIter<T> {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                let items: alloc::vec::Vec<&T> =
+                    self.iter.remaining().map(|slot| &slot.item).collect();
+                f.debug_tuple("IntoIter").field(&items).finish()
+            }
+        }
+
+        impl<T> Iterator for IntoIter<T> {
+            type Item = T;
+
+            #[inline]
+            fn next(&mut self) -> Option<T> {
+                if self.len == 0 {
+                    return None;
+                }
+
+                self.iter.next().map(|slot| {
+                    self.len -= 1;
+                    slot.item
+                })
+            }
+
+            #[inline]
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                (self.len, Some(self.len))
+            }
+        }
+
+        impl<T> ExactSizeIterator for IntoIter<T> {}
+
+        impl<T> core::iter::FusedIterator for IntoIter<T> {}
+    };
+}
+
+/// Implements `FromIterator`, `Extend` and `IntoIterator` for a heap which
+/// orders its elements with a comparator `C`, keeps its nodes in the pool
+/// `nodes` and whose iterators are defined by `iterators!` in the calling
+/// module.
+macro_rules! conversions {
+    ($heap:ident) => {
+        impl<T: Ord> core::iter::FromIterator<T> for $heap<T> {
+            fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> $heap<T> {
+                let mut heap = $heap::new();
+                heap.extend(iter);
+                heap
+            }
+        }
+
+        impl<T, C: $crate::compare::Compare<T>> Extend<T> for $heap<T, C> {
+            fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+                iter.into_iter().for_each(|item| {
+                    self.push(item);
+                });
+            }
+        }
+
+        impl<'a, T: 'a + Copy, C: $crate::compare::Compare<T>> Extend<&'a T> for $heap<T, C> {
+            fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+                self.extend(iter.into_iter().cloned());
+            }
+        }
+
+        impl<T, C> IntoIterator for $heap<T, C> {
+            type Item = T;
+            type IntoIter = IntoIter<T>;
+
+            fn into_iter(self) -> IntoIter<T> {
+                let len = self.nodes.len();
+                IntoIter::new(self.nodes.into_iter(), len)
+            }
+        }
+
+        impl<'a, T, C> IntoIterator for &'a $heap<T, C> {
+            type Item = &'a T;
+            type IntoIter = Iter<'a, T>;
+
+            fn into_iter(self) -> Iter<'a, T> {
+                self.iter()
+            }
+        }
+    };
+}
+
+pub(crate) use conversions;
+pub(crate) use iterators;
+
+#[cfg(test)]
+mod tests {
+    use crate::binomial_heap::BinomialHeap;
+    use crate::fibonacci_heap::FibonacciHeap;
+    use crate::pairing_heap::PairingHeap;
+    use crate::test_util::lcg;
+
+    /// The suite for the heaps which return a handle from each push. The
+    /// heap is created by the given expression, as a max-heap of `u64`.
+    macro_rules! handles {
+        ($name:ident, $new:expr) => {
+            mod $name {
+                use super::*;
+                use crate::arena::Handle;
+
+                #[test]
+                fn random_handles_pass() {
+                    let mut seed = 5u64;
+                    let mut h = $new;
+                    let mut live: Vec<(Handle, u64)> = Vec::new();
+
+                    // The values are unique, so the popped value names its handle.
+                    for step in 0..3000 {
+                        match lcg(&mut seed) % 6 {
+                            0 | 1 => {
+                                let x = lcg(&mut seed) % 1000 * 10_000 + step;
+                                live.push((h.push(x), x));
+                            }
+                            2 if !live.is_empty() => {
+                                let i = (lcg(&mut seed) as usize) % live.len();
+                                let x = lcg(&mut seed) % 1000 * 10_000 + step;
+                                if x >= live[i].1 {
+                                    h.increase_key(live[i].0, x);
+                                } else {
+                                    h.decrease_key(live[i].0, x);
+                                }
+                                live[i].1 = x;
+                                assert_eq!(Some(&x), h.get(live[i].0));
+                            }
+                            3 if !live.is_empty() => {
+                                let i = (lcg(&mut seed) as usize) % live.len();
+                                let (handle, x) = live.swap_remove(i);
+                                assert_eq!(Some(x), h.remove(handle));
+                            }
+                            _ => {
+                                let max = live.iter().map(|(_, x)| *x).max();
+                                assert_eq!(max, h.pop());
+                                if let Some(max) = max {
+                                    let i = live.iter().position(|(_, x)| *x == max).unwrap();
+                                    live.swap_remove(i);
+                                }
+                            }
+                        }
+
+                        assert_eq!(Ok(()), h.validate());
+                        assert_eq!(live.len(), h.len());
+                    }
+                }
+
+                #[test]
+                fn stale_handle_pass() {
+                    let mut h = $new;
+                    let a = h.push(1);
+                    assert_eq!(Some(1), h.pop());
+
+                    // The new element reuses the slot of the popped one.
+                    let b = h.push(2);
+                    assert_ne!(a, b);
+                    assert!(!h.contains(a));
+                    assert_eq!(None, h.get(a));
+                    assert_eq!(None, h.remove(a));
+                    assert_eq!(Some(&2), h.get(b));
+
+                    // The handles of another heap, or of a clone, are not valid.
+                    let mut other = $new;
+                    let c = other.push(3);
+                    assert_eq!(None, h.get(c));
+                    assert_eq!(None, other.clone().get(c));
+                }
+
+                #[test]
+                #[should_panic(expected = "the handle is not in the heap")]
+                fn stale_handle_fail() {
+                    let mut h = $new;
+                    let a = h.push(1);
+                    h.pop();
+                    h.push(2);
+                    h.increase_key(a, 10);
+                }
+
+                #[test]
+                fn append_handles_pass() {
+                    let mut a = $new;
+                    let mut b = $new;
+                    let ha = a.push(1);
+                    let hb = b.push(2);
+
+                    // The handles of both heaps name their elements in `a`.
+                    a.append(&mut b);
+                    assert_eq!(Some(&1), a.get(ha));
+                    assert_eq!(Some(&2), a.get(hb));
+                    assert!(!b.contains(hb));
+
+                    // So do the handles of a larger heap.
+                    let mut c = $new;
+                    let hc: Vec<Handle> = (10..15).map(|x| c.push(x)).collect();
+                    a.append(&mut c);
+                    assert!(c.is_empty());
+                    assert_eq!(Ok(()), a.validate());
+                    assert_eq!(Some(&1), a.get(ha));
+                    assert_eq!(Some(&2), a.get(hb));
+
+                    a.increase_key(hc[0], 20);
+                    assert_eq!(Some(20), a.pop());
+                    let xs: Vec<u64> = std::iter::from_fn(|| a.pop()).collect();
+                    assert_eq!(vec![14, 13, 12, 11, 2, 1], xs);
+                }
+            }
+        };
+    }
+
+    /// The iterators and conversions shared by the heaps with a comparator.
+    macro_rules! shared {
+        ($name:ident, $heap:ident) => {
+            mod $name {
+                use super::*;
+
+                #[test]
+                fn iter_pass() {
+                    let mut h: $heap<i32> = (0..100).collect();
+                    h.pop();
+
+                    let it = h.iter();
+                    assert_eq!(99, it.len());
+
+                    let mut xs: Vec<i32> = it.copied().collect();
+                    xs.sort_unstable();
+                    assert_eq!((0..99).collect::<Vec<_>>(), xs);
+                    assert!(format!("{:?}", h.iter()).starts_with("Iter"));
+                    assert_eq!(99, (&h).into_iter().count());
+                }
+
+                #[test]
+                fn into_iter_pass() {
+                    let h: $heap<i32> = (0..37).collect();
+
+                    let mut it = h.into_iter();
+                    it.next();
+                    assert_eq!(36, it.len());
+                    assert!(format!("{:?}", it).starts_with("IntoIter"));
+                    assert_eq!(36, it.count());
+                }
+
+                #[test]
+                fn from_iterator_pass() {
+                    let h: $heap<i32> = vec![3, 1, 4].into_iter().collect();
+                    assert_eq!(3, h.len());
+                    assert_eq!(Some(&4), h.peek());
+                }
+
+                #[test]
+                fn extend_pass() {
+                    let mut h = $heap::new_min();
+                    h.extend(vec![3, 1]);
+                    h.extend(&[4, 0]);
+                    assert_eq!(4, h.len());
+                    assert_eq!(Some(0), h.pop());
+                }
+            }
+        };
+    }
+
+    handles!(binomial_heap_handles, BinomialHeap::<u64>::new());
+    handles!(fibonacci_heap_handles, FibonacciHeap::<u64>::new());
+    handles!(pairing_heap_handles, PairingHeap::<u64>::new());
+
+    shared!(fibonacci_heap, FibonacciHeap);
+    shared!(pairing_heap, PairingHeap);
+}
@@ -0,0 +1,49 @@
+/// Iterators over the keys in an indexed heap.
+use std::fmt::Debug;
+use std::iter::FusedIterator;
+use std::slice;
+
+/// An iterator over the keys and the priorities of an `IndexedHeap`,
+/// in arbitrary order.
+pub struct Iter<'a, K: 'a, P: 'a> {
+    iter: slice::Iter<'a, (K, P)>,
+}
+
+impl<'a, K, P> Iter<'a, K, P> {
+    /// Creates a new instance of the iterator.
+    pub fn new(iter: slice::Iter<'a, (K, P)>) -> Self {
+        Self { iter }
+    }
+}
+
+impl<K: Debug, P: Debug> Debug for Iter<'_, K, P> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Iter").field(&self.iter.as_slice()).finish()
+    }
+}
+
+impl<K, P> Clone for Iter<'_, K, P> {
+    fn clone(&self) -> Self {
+        Iter {
+            iter: self.iter.clone(),
+        }
+    }
+}
+
+impl<'a, K, P> Iterator for Iter<'a, K, P> {
+    type Item = (&'a K, &'a P);
+
+    #[inline]
+    fn next(&mut self) -> Option<(&'a K, &'a P)> {
+        self.iter.next().map(|(key, priority)| (key, priority))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<K, P> ExactSizeIterator for Iter<'_, K, P> {}
+
+impl<K, P> FusedIterator for Iter<'_, K, P> {}
@@ -0,0 +1,492 @@
+//! Implementation of the indexed heap
+use super::iter::*;
+use crate::binary_heap::BinaryHeap;
+use crate::compare::{Compare, MaxComparator, MinComparator};
+use crate::invariant::InvariantError;
+
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::hash::Hash;
+
+/// Orders the pairs of a key and a priority by their priorities.
+#[derive(Clone)]
+pub(crate) struct ByPriority<C>(C);
+
+impl<K, P, C: Compare<P>> Compare<(K, P)> for ByPriority<C> {
+    #[inline]
+    fn compare(&self, a: &(K, P), b: &(K, P)) -> Ordering {
+        self.0.compare(&a.1, &b.1)
+    }
+}
+
+/// A binary heap of keys ordered by their priorities. Every key is stored
+/// once and a map keeps the position of each key in the heap, so the
+/// priority of a key can be changed, or the key removed, in O(log n).
+/// The pairs are kept in a `BinaryHeap`, whose sifts report every move
+/// to keep the map in sync.
+///
+/// The order of the priorities is given by the comparator `C`. By default
+/// the heap is a max-heap, which keeps the key with the greatest priority
+/// at the top.
+pub struct IndexedHeap<K, P, C = MaxComparator> {
+    pub(crate) heap: BinaryHeap<(K, P), ByPriority<C>>,
+    positions: HashMap<K, usize>,
+}
+
+impl<K: Clone, P: Clone, C: Clone> Clone for IndexedHeap<K, P, C> {
+    fn clone(&self) -> Self {
+        Self {
+            heap: self.heap.clone(),
+            positions: self.positions.clone(),
+        }
+    }
+}
+
+impl<K, P, C> Default for IndexedHeap<K, P, C>
+where
+    K: Hash + Eq + Clone,
+    C: Compare<P> + Default,
+{
+    fn default() -> Self {
+        Self::from_comparator(C::default())
+    }
+}
+
+impl<K: Debug, P: Debug, C> Debug for IndexedHeap<K, P, C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K: Hash + Eq + Clone, P: Ord> IndexedHeap<K, P> {
+    /// Creates a new instance of the indexed heap.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::indexed_heap::IndexedHeap;
+    ///
+    /// let mut heap = IndexedHeap::new();
+    /// heap.push("a", 1);
+    /// heap.push("b", 3);
+    ///
+    /// assert_eq!(Some((&"b", &3)), heap.peek());
+    /// ```
+    pub fn new() -> Self {
+        Self::from_comparator(MaxComparator)
+    }
+
+    /// Creates a new instance of the indexed heap with a given capacity.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            heap: BinaryHeap {
+                data: Vec::with_capacity(capacity),
+                cmp: ByPriority(MaxComparator),
+            },
+            positions: HashMap::with_capacity(capacity),
+        }
+    }
+}
+
+impl<K: Hash + Eq + Clone, P: Ord> IndexedHeap<K, P, MinComparator> {
+    /// Creates a new instance of a min-heap, which keeps
+    /// the key with the smallest priority at the top.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::indexed_heap::IndexedHeap;
+    ///
+    /// let mut heap = IndexedHeap::new_min();
+    /// heap.push("a", 1);
+    /// heap.push("b", 3);
+    ///
+    /// assert_eq!(Some(("a", 1)), heap.pop());
+    /// ```
+    pub fn new_min() -> Self {
+        Self::from_comparator(MinComparator)
+    }
+}
+
+impl<K: Hash + Eq + Clone, P, C: Compare<P>> IndexedHeap<K, P, C> {
+    /// Creates a new instance of the indexed heap which orders
+    /// the priorities with the given comparator.
+    pub fn from_comparator(cmp: C) -> Self {
+        Self {
+            heap: BinaryHeap::from_comparator(ByPriority(cmp)),
+            positions: HashMap::new(),
+        }
+    }
+
+    /// Pushes a key with its priority into the indexed heap. If the key
+    /// is already in the heap, its priority is changed and the old
+    /// priority is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::indexed_heap::IndexedHeap;
+    ///
+    /// let mut heap = IndexedHeap::new();
+    /// assert_eq!(None, heap.push("a", 1));
+    /// assert_eq!(Some(1), heap.push("a", 5));
+    ///
+    /// assert_eq!(1, heap.len());
+    /// assert_eq!(Some(&5), heap.get_priority("a"));
+    /// ```
+    pub fn push(&mut self, key: K, priority: P) -> Option<P> {
+        if self.positions.contains_key(&key) {
+            return self.change_priority(&key, priority);
+        }
+
+        let pos = self.heap.data.len();
+        self.positions.insert(key.clone(), pos);
+        self.heap.data.push((key, priority));
+        self.bubble_up(pos);
+
+        None
+    }
+
+    /// Removes the key with the greatest priority from the indexed heap
+    /// and returns it with its priority, or `None` if the heap is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::indexed_heap::IndexedHeap;
+    ///
+    /// let mut heap = IndexedHeap::new();
+    /// heap.push('a', 2);
+    /// heap.push('b', 1);
+    ///
+    /// assert_eq!(Some(('a', 2)), heap.pop());
+    /// assert_eq!(Some(('b', 1)), heap.pop());
+    /// assert_eq!(None, heap.pop());
+    /// ```
+    pub fn pop(&mut self) -> Option<(K, P)> {
+        if self.heap.is_empty() {
+            None
+        } else {
+            Some(self.remove_at(0))
+        }
+    }
+
+    /// Changes the priority of a key and returns the old priority,
+    /// or `None` if the key is not in the indexed heap.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::indexed_heap::IndexedHeap;
+    ///
+    /// let mut heap = IndexedHeap::new_min();
+    /// heap.push("a", 10);
+    /// heap.push("b", 20);
+    ///
+    /// assert_eq!(Some(20), heap.change_priority("b", 5));
+    /// assert_eq!(None, heap.change_priority("c", 1));
+    /// assert_eq!(Some(("b", 5)), heap.pop());
+    /// ```
+    pub fn change_priority<Q>(&mut self, key: &Q, priority: P) -> Option<P>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let pos = *self.positions.get(key)?;
+        let BinaryHeap { data, cmp } = &mut self.heap;
+        let old = std::mem::replace(&mut data[pos].1, priority);
+
+        if cmp.0.compare(&data[pos].1, &old).is_gt() {
+            self.bubble_up(pos);
+        } else {
+            self.bubble_down(pos);
+        }
+
+        Some(old)
+    }
+
+    /// Removes a key from the indexed heap and returns its priority,
+    /// or `None` if the key is not in the heap.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::indexed_heap::IndexedHeap;
+    ///
+    /// let mut heap = IndexedHeap::new();
+    /// heap.push("a", 1);
+    /// heap.push("b", 2);
+    ///
+    /// assert_eq!(Some(2), heap.remove("b"));
+    /// assert_eq!(None, heap.remove("b"));
+    /// assert_eq!(Some(("a", 1)), heap.pop());
+    /// ```
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<P>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let pos = *self.positions.get(key)?;
+        Some(self.remove_at(pos).1)
+    }
+
+    /// Checks that the priority of every key is smaller or equal than the
+    /// priority of its parent, and that the map holds the position of
+    /// every key.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::indexed_heap::IndexedHeap;
+    ///
+    /// let mut heap = IndexedHeap::new();
+    /// heap.push("a", 1);
+    /// heap.push("b", 2);
+    /// assert!(heap.validate().is_ok());
+    /// ```
+    pub fn validate(&self) -> Result<(), InvariantError> {
+        if self.positions.len() != self.heap.len() {
+            return Err(InvariantError::Length {
+                expected: self.heap.len(),
+                found: self.positions.len(),
+            });
+        }
+
+        for (node, (key, _)) in self.heap.data.iter().enumerate() {
+            if self.positions.get(key) != Some(&node) {
+                return Err(InvariantError::Position { node });
+            }
+        }
+
+        self.heap.validate()
+    }
+
+    /// Removes the key at `pos` and fills the gap with the last key.
+    fn remove_at(&mut self, pos: usize) -> (K, P) {
+        let (key, priority) = self.heap.data.swap_remove(pos);
+        self.positions.remove(&key);
+
+        if pos < self.heap.len() {
+            // The last key took the place of the removed one.
+            let pos = self.bubble_up(pos);
+            self.bubble_down(pos);
+        }
+
+        (key, priority)
+    }
+
+    /// Bubbles up into the heap the key at 'pos' and returns its new position.
+    fn bubble_up(&mut self, pos: usize) -> usize {
+        let positions = &mut self.positions;
+        self.heap.sift_up_with(0, pos, |(key, _), pos| {
+            Self::set_position(positions, key, pos)
+        })
+    }
+
+    /// Bubbles down into the heap the key at 'pos'.
+    fn bubble_down(&mut self, pos: usize) {
+        let positions = &mut self.positions;
+        let end = self.heap.len();
+        self.heap.sift_down_with(pos, end, |(key, _), pos| {
+            Self::set_position(positions, key, pos)
+        });
+    }
+
+    /// Records the position of a key.
+    fn set_position(positions: &mut HashMap<K, usize>, key: &K, pos: usize) {
+        *positions.get_mut(key).expect("the key is in the heap") = pos;
+    }
+}
+
+impl<K: Hash + Eq, P, C> IndexedHeap<K, P, C> {
+    /// Returns true if the key is in the indexed heap.
+    pub fn contains<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.positions.contains_key(key)
+    }
+
+    /// Returns the priority of a key, or `None` if the key
+    /// is not in the indexed heap.
+    pub fn get_priority<Q>(&self, key: &Q) -> Option<&P>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.positions.get(key).map(|pos| &self.heap.data[*pos].1)
+    }
+}
+
+impl<K, P, C> IndexedHeap<K, P, C> {
+    /// Returns the key with the greatest priority, and its priority.
+    pub fn peek(&self) -> Option<(&K, &P)> {
+        self.heap.peek().map(|(key, priority)| (key, priority))
+    }
+
+    /// Returns an iterator over the keys and their priorities,
+    /// in arbitrary order.
+    pub fn iter(&self) -> Iter<'_, K, P> {
+        Iter::new(self.heap.data.iter())
+    }
+
+    /// Removes all the keys from the indexed heap.
+    pub fn clear(&mut self) {
+        self.heap.clear();
+        self.positions.clear();
+    }
+
+    /// Returns the number of keys in the indexed heap.
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// Returns a flag indicating whenever the indexed heap is empty.
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+}
+
+impl<K, P, C> Extend<(K, P)> for IndexedHeap<K, P, C>
+where
+    K: Hash + Eq + Clone,
+    C: Compare<P>,
+{
+    fn extend<I: IntoIterator<Item = (K, P)>>(&mut self, iter: I) {
+        for (key, priority) in iter {
+            self.push(key, priority);
+        }
+    }
+}
+
+impl<K: Hash + Eq + Clone, P: Ord> std::iter::FromIterator<(K, P)> for IndexedHeap<K, P> {
+    fn from_iter<I: IntoIterator<Item = (K, P)>>(iter: I) -> Self {
+        let mut heap = IndexedHeap::new();
+        heap.extend(iter);
+        heap
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::lcg;
+
+    #[test]
+    fn new_pass() {
+        let h = IndexedHeap::<u8, u8>::new();
+        assert!(h.is_empty());
+        assert_eq!(None, h.peek());
+    }
+
+    #[test]
+    fn debug_pass() {
+        let mut h = IndexedHeap::new();
+        h.push("a", 1);
+        assert_eq!("{\"a\": 1}", format!("{:?}", h));
+    }
+
+    #[test]
+    fn push_pop_pass() {
+        let mut h = IndexedHeap::new();
+        for (k, p) in [("a", 3), ("b", 1), ("c", 4), ("d", 2)].iter() {
+            h.push(*k, *p);
+        }
+        assert_eq!(Ok(()), h.validate());
+        assert_eq!(4, h.len());
+
+        assert_eq!(Some(("c", 4)), h.pop());
+        assert_eq!(Some(("a", 3)), h.pop());
+        assert_eq!(Some(("d", 2)), h.pop());
+        assert_eq!(Some(("b", 1)), h.pop());
+        assert_eq!(None, h.pop());
+        assert!(!h.contains("a"));
+    }
+
+    #[test]
+    fn change_priority_pass() {
+        let mut h: IndexedHeap<u32, u32> = (0..10).map(|x| (x, x)).collect();
+
+        assert_eq!(Some(0), h.change_priority(&0, 100));
+        assert_eq!(Ok(()), h.validate());
+        assert_eq!(Some((&0, &100)), h.peek());
+
+        assert_eq!(Some(100), h.change_priority(&0, 0));
+        assert_eq!(Ok(()), h.validate());
+        assert_eq!(Some((&9, &9)), h.peek());
+
+        assert_eq!(None, h.change_priority(&10, 0));
+    }
+
+    #[test]
+    fn remove_pass() {
+        let mut h: IndexedHeap<u32, u32> = (0..10).map(|x| (x, x)).collect();
+        assert_eq!(Some(5), h.remove(&5));
+        assert_eq!(Some(9), h.remove(&9));
+        assert_eq!(None, h.remove(&9));
+        assert_eq!(Ok(()), h.validate());
+
+        let xs: Vec<u32> = std::iter::from_fn(|| h.pop().map(|(k, _)| k)).collect();
+        assert_eq!(vec![8, 7, 6, 4, 3, 2, 1, 0], xs);
+    }
+
+    #[test]
+    fn get_priority_pass() {
+        let mut h = IndexedHeap::new();
+        h.push(String::from("a"), 1);
+        assert!(h.contains("a"));
+        assert_eq!(Some(&1), h.get_priority("a"));
+        assert_eq!(None, h.get_priority("b"));
+    }
+
+    #[test]
+    fn random_pass() {
+        let mut seed = 11u64;
+        let mut h = IndexedHeap::new_min();
+        let mut model = HashMap::new();
+
+        for _ in 0..2000 {
+            let key = lcg(&mut seed) % 64;
+            let priority = lcg(&mut seed) % 1000;
+            match lcg(&mut seed) % 4 {
+                0 | 1 => {
+                    assert_eq!(model.insert(key, priority), h.push(key, priority));
+                }
+                2 => {
+                    assert_eq!(model.remove(&key), h.remove(&key));
+                }
+                _ => {
+                    if let Some((k, p)) = h.pop() {
+                        let min = model.values().min().copied();
+                        assert_eq!(Some(p), min);
+                        assert_eq!(Some(p), model.remove(&k));
+                    }
+                }
+            }
+
+            assert_eq!(Ok(()), h.validate());
+            assert_eq!(model.len(), h.len());
+        }
+    }
+
+    #[test]
+    fn validate_fail() {
+        let mut h = IndexedHeap::new();
+        h.push("a", 1);
+        h.push("b", 2);
+        h.heap.data.swap(0, 1);
+        assert_eq!(Err(InvariantError::Position { node: 0 }), h.validate());
+    }
+
+    #[test]
+    fn clear_pass() {
+        let mut h = IndexedHeap::new();
+        h.push("a", 1);
+        h.clear();
+        assert!(h.is_empty());
+        assert!(!h.contains("a"));
+    }
+}
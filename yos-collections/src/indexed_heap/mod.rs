@@ -0,0 +1,6 @@
+//! Indexed heap
+mod iter;
+mod main;
+
+pub use crate::indexed_heap::iter::*;
+pub use crate::indexed_heap::main::IndexedHeap;
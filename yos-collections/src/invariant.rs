@@ -0,0 +1,164 @@
+//! Errors reported when a heap breaks one of its invariants.
+use core::fmt;
+
+/// Describes the first invariant violation found when validating a heap.
+///
+/// The nodes are named by their position: the index in the vector for the
+/// binary and d-ary heaps, and the depth-first position (each node before
+/// its children, the children before the next sibling) for the binomial,
+/// Fibonacci and pairing heaps and a chain of binomial heap nodes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InvariantError {
+    /// A child is greater than its parent.
+    Order {
+        /// The parent node.
+        parent: usize,
+        /// The child node, which is greater than the parent.
+        child: usize,
+    },
+    /// A node of a min-max heap is out of order with an ancestor on a min
+    /// or max level.
+    Level {
+        /// The ancestor node.
+        ancestor: usize,
+        /// The node, which is smaller than a max ancestor or greater than a
+        /// min ancestor.
+        node: usize,
+    },
+    /// The orders along the chain of roots are not strictly increasing.
+    RootOrder {
+        /// The root node which breaks the chain.
+        node: usize,
+        /// The order of the root node.
+        order: usize,
+        /// The order of the previous root in the chain.
+        previous: usize,
+    },
+    /// A child of a node has an unexpected order.
+    ChildOrder {
+        /// The parent node.
+        node: usize,
+        /// The expected order of the child.
+        expected: usize,
+        /// The order of the child.
+        found: usize,
+    },
+    /// A node of order `k` does not have exactly `k` children.
+    ChildCount {
+        /// The node.
+        node: usize,
+        /// The order of the node.
+        order: usize,
+        /// The number of children of the node.
+        children: usize,
+    },
+    /// A node does not link back to its parent.
+    Parent {
+        /// The node with the broken link.
+        node: usize,
+    },
+    /// A root is greater than the root at the top of the heap.
+    Top {
+        /// The root which is greater than the top.
+        node: usize,
+    },
+    /// A node is not linked back by the next node in its list of siblings.
+    Sibling {
+        /// The node with the broken link.
+        node: usize,
+    },
+    /// The position recorded for a node does not match its index.
+    Position {
+        /// The node with the wrong position.
+        node: usize,
+    },
+    /// The length of the heap does not match the number of elements.
+    Length {
+        /// The length recorded by the heap.
+        expected: usize,
+        /// The number of elements found in the heap.
+        found: usize,
+    },
+}
+
+impl fmt::Display for InvariantError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvariantError::Order { parent, child } => {
+                write!(f, "node {} is greater than its parent {}", child, parent)
+            }
+            InvariantError::Level { ancestor, node } => {
+                write!(
+                    f,
+                    "node {} is out of order with its ancestor {}",
+                    node, ancestor
+                )
+            }
+            InvariantError::RootOrder {
+                node,
+                order,
+                previous,
+            } => write!(
+                f,
+                "root {} has order {} which does not follow the order {} of the previous root",
+                node, order, previous
+            ),
+            InvariantError::ChildOrder {
+                node,
+                expected,
+                found,
+            } => write!(
+                f,
+                "node {} has a child of order {} where order {} was expected",
+                node, found, expected
+            ),
+            InvariantError::ChildCount {
+                node,
+                order,
+                children,
+            } => write!(
+                f,
+                "node {} has order {} but {} children",
+                node, order, children
+            ),
+            InvariantError::Parent { node } => {
+                write!(f, "node {} does not link back to its parent", node)
+            }
+            InvariantError::Top { node } => {
+                write!(f, "root {} is greater than the top of the heap", node)
+            }
+            InvariantError::Sibling { node } => {
+                write!(f, "node {} is not linked back by its sibling", node)
+            }
+            InvariantError::Position { node } => {
+                write!(f, "node {} is not at its recorded position", node)
+            }
+            InvariantError::Length { expected, found } => {
+                write!(f, "the heap has length {} but {} elements", expected, found)
+            }
+        }
+    }
+}
+
+impl core::error::Error for InvariantError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_pass() {
+        let e = InvariantError::Order {
+            parent: 1,
+            child: 3,
+        };
+        assert_eq!("node 3 is greater than its parent 1", e.to_string());
+
+        let e = InvariantError::ChildCount {
+            node: 0,
+            order: 2,
+            children: 1,
+        };
+        assert_eq!("node 0 has order 2 but 1 children", e.to_string());
+    }
+}
@@ -1,7 +1,33 @@
 //! A rust library for collection data structures.
 
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 #![deny(missing_docs)]
 #![deny(unreachable_pub)]
 
+extern crate alloc;
+
+mod arena;
+mod dot;
+mod handle_heap;
+mod pool;
+#[cfg(test)]
+pub(crate) mod test_util;
+
 pub mod binary_heap;
 pub mod binomial_heap;
+pub mod compare;
+pub mod dary_heap;
+pub mod fibonacci_heap;
+#[cfg(feature = "std")]
+pub mod indexed_heap;
+pub mod invariant;
+pub mod merge;
+pub mod min_max_heap;
+#[cfg(feature = "std")]
+pub mod multi_queue;
+pub mod pairing_heap;
+pub mod priority_queue;
+pub mod radix_heap;
+pub mod running_median;
+pub mod stable_binary_heap;
+pub mod top_k;
@@ -0,0 +1,393 @@
+//! Lazy merge of many sorted iterators into one sorted iterator.
+use crate::binary_heap::{BinaryHeap, PeekMut};
+use crate::compare::{Compare, FnComparator, MaxComparator};
+
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt::Debug;
+use core::iter::FusedIterator;
+use core::mem::replace;
+
+/// The next element of a source, together with the index of the source.
+#[derive(Clone, Debug)]
+pub(crate) struct Head<T> {
+    item: T,
+    source: usize,
+}
+
+/// Orders the heads so the least element is at the top of the heap.
+/// The ties are broken by the index of the source, which makes the
+/// merge stable.
+#[derive(Clone, Debug)]
+pub(crate) struct HeadOrder<C>(C);
+
+impl<T, C: Compare<T>> Compare<Head<T>> for HeadOrder<C> {
+    #[inline]
+    fn compare(&self, a: &Head<T>, b: &Head<T>) -> Ordering {
+        self.0
+            .compare(&b.item, &a.item)
+            .then_with(|| b.source.cmp(&a.source))
+    }
+}
+
+/// An iterator which merges sorted sources into one sorted stream, returned
+/// by `kmerge` and `kmerge_by`.
+///
+/// The head of every source is kept in a binary heap, so each element costs
+/// O(log k) comparisons for k sources. The sources are expected to be sorted
+/// in ascending order by the comparator `C`, the equal elements are yielded
+/// in the order of their sources.
+pub struct KMerge<I: Iterator, C = MaxComparator> {
+    heap: BinaryHeap<Head<I::Item>, HeadOrder<C>>,
+    sources: Vec<I>,
+}
+
+impl<I, C> KMerge<I, C>
+where
+    I: Iterator,
+    C: Compare<I::Item>,
+{
+    /// Creates a merge of the sources, which are sorted in ascending order
+    /// by the given comparator.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::compare::MinComparator;
+    /// use yos_collections::merge::KMerge;
+    ///
+    /// let xs: Vec<_> = KMerge::from_comparator(vec![vec![5, 1], vec![4, 3, 2]], MinComparator).collect();
+    /// assert_eq!(vec![5, 4, 3, 2, 1], xs);
+    /// ```
+    pub fn from_comparator<J>(iters: J, cmp: C) -> Self
+    where
+        J: IntoIterator,
+        J::Item: IntoIterator<IntoIter = I, Item = I::Item>,
+    {
+        let mut sources: Vec<I> = iters.into_iter().map(IntoIterator::into_iter).collect();
+
+        let mut heap = BinaryHeap::from_comparator(HeadOrder(cmp));
+        heap.extend(
+            sources
+                .iter_mut()
+                .enumerate()
+                .filter_map(|(source, iter)| iter.next().map(|item| Head { item, source })),
+        );
+
+        Self { heap, sources }
+    }
+
+    /// Skips the equal elements, according to the comparator,
+    /// so every element is yielded only once.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::merge::kmerge;
+    ///
+    /// let xs: Vec<_> = kmerge(vec![vec![1, 2, 2], vec![2, 3]]).dedup().collect();
+    /// assert_eq!(vec![1, 2, 3], xs);
+    /// ```
+    pub fn dedup(self) -> Dedup<I, C> {
+        Dedup { merge: self }
+    }
+}
+
+impl<I, C> Clone for KMerge<I, C>
+where
+    I: Iterator + Clone,
+    I::Item: Clone,
+    C: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            heap: self.heap.clone(),
+            sources: self.sources.clone(),
+        }
+    }
+}
+
+impl<I, C> Debug for KMerge<I, C>
+where
+    I: Iterator,
+    I::Item: Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let heads: Vec<&I::Item> = self.heap.iter().map(|head| &head.item).collect();
+        f.debug_tuple("KMerge").field(&heads).finish()
+    }
+}
+
+impl<I, C> Iterator for KMerge<I, C>
+where
+    I: Iterator,
+    C: Compare<I::Item>,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        let mut top = self.heap.peek_mut()?;
+
+        // Replace the head with the next element of the same source,
+        // the guard sifts it down when dropped.
+        match self.sources[top.source].next() {
+            Some(item) => Some(replace(&mut top.item, item)),
+            None => Some(PeekMut::pop(top).item),
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.heap.len();
+        self.sources
+            .iter()
+            .map(Iterator::size_hint)
+            .fold((len, Some(len)), |(lo, hi), (l, h)| {
+                let hi = match (hi, h) {
+                    (Some(hi), Some(h)) => hi.checked_add(h),
+                    _ => None,
+                };
+                (lo.saturating_add(l), hi)
+            })
+    }
+}
+
+impl<I, C> FusedIterator for KMerge<I, C>
+where
+    I: Iterator,
+    C: Compare<I::Item>,
+{
+}
+
+/// An iterator which merges sorted sources into one sorted stream and
+/// yields only one of the equal elements, returned by `KMerge::dedup`
+/// and `kmerge_dedup`. The first of the equal elements is kept.
+pub struct Dedup<I: Iterator, C = MaxComparator> {
+    merge: KMerge<I, C>,
+}
+
+impl<I, C> Clone for Dedup<I, C>
+where
+    I: Iterator + Clone,
+    I::Item: Clone,
+    C: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            merge: self.merge.clone(),
+        }
+    }
+}
+
+impl<I, C> Debug for Dedup<I, C>
+where
+    I: Iterator,
+    I::Item: Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("Dedup").field(&self.merge).finish()
+    }
+}
+
+impl<I, C> Iterator for Dedup<I, C>
+where
+    I: Iterator,
+    C: Compare<I::Item>,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        let item = self.merge.next()?;
+
+        // The sources are sorted, so the equal elements are at the top.
+        while let Some(head) = self.merge.heap.peek() {
+            if self.merge.heap.cmp.0.compare(&head.item, &item).is_ne() {
+                break;
+            }
+            self.merge.next();
+        }
+
+        Some(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lo, hi) = self.merge.size_hint();
+        (lo.min(1), hi)
+    }
+}
+
+impl<I, C> FusedIterator for Dedup<I, C>
+where
+    I: Iterator,
+    C: Compare<I::Item>,
+{
+}
+
+/// Merges iterators sorted in ascending order into one sorted iterator.
+/// The elements are pulled from the sources only when needed. The equal
+/// elements are yielded in the order of their sources.
+///
+/// # Example
+///
+/// ```
+/// use yos_collections::merge::kmerge;
+///
+/// let shards = vec![vec![1, 4, 7], vec![2, 5], vec![3, 6, 8]];
+/// let xs: Vec<_> = kmerge(shards).collect();
+///
+/// assert_eq!(vec![1, 2, 3, 4, 5, 6, 7, 8], xs);
+/// ```
+pub fn kmerge<J>(iters: J) -> KMerge<<J::Item as IntoIterator>::IntoIter>
+where
+    J: IntoIterator,
+    J::Item: IntoIterator,
+    <J::Item as IntoIterator>::Item: Ord,
+{
+    KMerge::from_comparator(iters, MaxComparator)
+}
+
+/// Merges iterators sorted in ascending order by a closure into one
+/// sorted iterator.
+///
+/// # Example
+///
+/// ```
+/// use yos_collections::merge::kmerge_by;
+///
+/// let logs = vec![vec![(1, "a"), (5, "b")], vec![(2, "c"), (5, "d")]];
+/// let xs: Vec<_> = kmerge_by(logs, |a: &(u32, &str), b: &(u32, &str)| a.0.cmp(&b.0)).collect();
+///
+/// assert_eq!(vec![(1, "a"), (2, "c"), (5, "b"), (5, "d")], xs);
+/// ```
+pub fn kmerge_by<J, F>(
+    iters: J,
+    f: F,
+) -> KMerge<<J::Item as IntoIterator>::IntoIter, FnComparator<F>>
+where
+    J: IntoIterator,
+    J::Item: IntoIterator,
+    F: Fn(&<J::Item as IntoIterator>::Item, &<J::Item as IntoIterator>::Item) -> Ordering,
+{
+    KMerge::from_comparator(iters, FnComparator(f))
+}
+
+/// Merges iterators sorted in ascending order into one sorted iterator,
+/// which yields only one of the equal elements.
+///
+/// # Example
+///
+/// ```
+/// use yos_collections::merge::kmerge_dedup;
+///
+/// let xs: Vec<_> = kmerge_dedup(vec![vec![1, 3, 5], vec![1, 2, 3]]).collect();
+/// assert_eq!(vec![1, 2, 3, 5], xs);
+/// ```
+pub fn kmerge_dedup<J>(iters: J) -> Dedup<<J::Item as IntoIterator>::IntoIter>
+where
+    J: IntoIterator,
+    J::Item: IntoIterator,
+    <J::Item as IntoIterator>::Item: Ord,
+{
+    kmerge(iters).dedup()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::lcg;
+
+    fn runs(seed: &mut u64, count: usize) -> Vec<Vec<u64>> {
+        (0..count)
+            .map(|_| {
+                let len = (lcg(seed) % 50) as usize;
+                let mut run: Vec<u64> = (0..len).map(|_| lcg(seed) % 100).collect();
+                run.sort_unstable();
+                run
+            })
+            .collect()
+    }
+
+    #[test]
+    fn kmerge_pass() {
+        let mut seed = 3u64;
+        let xs = runs(&mut seed, 20);
+
+        let mut expected: Vec<u64> = xs.iter().flatten().cloned().collect();
+        expected.sort_unstable();
+
+        let merge = kmerge(xs);
+        assert_eq!(expected.len(), merge.size_hint().0);
+        assert_eq!(expected, merge.collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn kmerge_empty_pass() {
+        let xs: Vec<Vec<u8>> = vec![];
+        assert_eq!(None, kmerge(xs).next());
+
+        let xs: Vec<Vec<u8>> = vec![vec![], vec![1], vec![]];
+        assert_eq!(vec![1], kmerge(xs).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn kmerge_lazy_pass() {
+        // Infinite sources are merged lazily.
+        let evens = (0u64..).map(|x| x * 2);
+        let odds = (0u64..).map(|x| x * 2 + 1);
+
+        let xs: Vec<u64> = kmerge(vec![
+            Box::new(evens) as Box<dyn Iterator<Item = u64>>,
+            Box::new(odds),
+        ])
+        .take(6)
+        .collect();
+        assert_eq!(vec![0, 1, 2, 3, 4, 5], xs);
+    }
+
+    #[test]
+    fn kmerge_stable_pass() {
+        let xs = vec![
+            vec![(1, 'a'), (2, 'b')],
+            vec![(1, 'c')],
+            vec![(1, 'd'), (2, 'e')],
+        ];
+        let ys: String = kmerge_by(xs, |a: &(u8, char), b: &(u8, char)| a.0.cmp(&b.0))
+            .map(|x| x.1)
+            .collect();
+        assert_eq!("acdbe", ys);
+    }
+
+    #[test]
+    fn kmerge_by_pass() {
+        let xs = vec![vec![9, 4, 1], vec![8, 7], vec![6, 3, 2]];
+        let ys: Vec<_> = kmerge_by(xs, |a: &i32, b: &i32| b.cmp(a)).collect();
+        assert_eq!(vec![9, 8, 7, 6, 4, 3, 2, 1], ys);
+    }
+
+    #[test]
+    fn dedup_pass() {
+        let mut seed = 5u64;
+        let xs = runs(&mut seed, 10);
+
+        let mut expected: Vec<u64> = xs.iter().flatten().cloned().collect();
+        expected.sort_unstable();
+        expected.dedup();
+
+        assert_eq!(expected, kmerge_dedup(xs).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn dedup_by_pass() {
+        let xs = vec![vec![(1, 'a'), (2, 'b')], vec![(1, 'c'), (3, 'd')]];
+        let ys: String = kmerge_by(xs, |a: &(u8, char), b: &(u8, char)| a.0.cmp(&b.0))
+            .dedup()
+            .map(|x| x.1)
+            .collect();
+        assert_eq!("abd", ys);
+    }
+
+    #[test]
+    fn debug_pass() {
+        let merge = kmerge(vec![vec![1, 2], vec![3]]);
+        assert_eq!("KMerge([1, 3])", format!("{:?}", merge));
+    }
+}
@@ -0,0 +1,83 @@
+use super::MinMaxHeap;
+use alloc::vec::Vec;
+use core::iter::FromIterator;
+
+impl<T: Ord> From<Vec<T>> for MinMaxHeap<T> {
+    /// Converts a `Vec<T>` into a `MinMaxHeap<T>`.
+    ///
+    /// This conversion happens in-place, and has `O(n)` time complexity.
+    fn from(vec: Vec<T>) -> Self {
+        let mut heap = Self { data: vec };
+        heap.rebuild();
+        heap
+    }
+}
+
+impl<T> From<MinMaxHeap<T>> for Vec<T> {
+    fn from(heap: MinMaxHeap<T>) -> Vec<T> {
+        heap.data
+    }
+}
+
+impl<T: Ord> FromIterator<T> for MinMaxHeap<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> MinMaxHeap<T> {
+        MinMaxHeap::from(iter.into_iter().collect::<Vec<_>>())
+    }
+}
+
+impl<T: Ord> Extend<T> for MinMaxHeap<T> {
+    /// Extends the min-max heap with the elements of an iterator. When more
+    /// elements arrive than the heap already has, the heap is rebuilt
+    /// instead of pushing them one by one.
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let start = self.data.len();
+        self.data.extend(iter);
+
+        if self.data.len() - start > start {
+            self.rebuild();
+        } else {
+            (start..self.data.len()).for_each(|pos| self.bubble_up(pos));
+        }
+    }
+}
+
+impl<'a, T: 'a + Ord + Copy> Extend<&'a T> for MinMaxHeap<T> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().cloned());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_vec_to_vec() {
+        let mut h = MinMaxHeap::from(vec![3, 1, 4]);
+        h.push(2);
+
+        let xs: Vec<i32> = h.into();
+        assert_eq!(xs.len(), 4);
+    }
+
+    #[test]
+    fn from_iterator() {
+        let h: MinMaxHeap<_> = (0..100).collect();
+        assert_eq!(Ok(()), h.validate());
+        assert_eq!(Some(&0), h.peek_min());
+        assert_eq!(Some(&99), h.peek_max());
+    }
+
+    #[test]
+    fn extend_pass() {
+        let mut h = MinMaxHeap::from((0..100).collect::<Vec<_>>());
+        h.extend(&[200, -5]);
+        assert_eq!(Ok(()), h.validate());
+        assert_eq!(Some(&-5), h.peek_min());
+        assert_eq!(Some(&200), h.peek_max());
+
+        h.extend(1000..2000);
+        assert_eq!(Ok(()), h.validate());
+        assert_eq!(Some(&1999), h.peek_max());
+    }
+}
@@ -0,0 +1,39 @@
+//! Iterators over the elements in a min-max heap.
+use super::MinMaxHeap;
+use crate::binary_heap::{IntoIter, Iter};
+
+impl<T> IntoIterator for MinMaxHeap<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter::new(self.data.into_iter())
+    }
+}
+
+impl<'a, T> IntoIterator for &'a MinMaxHeap<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn into_iter_pass() {
+        let h = MinMaxHeap::from(vec![1, 2, 3]);
+
+        let mut xs: Vec<&i32> = (&h).into_iter().collect();
+        xs.sort_unstable();
+        assert_eq!(vec![&1, &2, &3], xs);
+
+        let mut xs: Vec<i32> = h.into_iter().collect();
+        xs.sort_unstable();
+        assert_eq!(vec![1, 2, 3], xs);
+    }
+}
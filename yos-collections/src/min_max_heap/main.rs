@@ -0,0 +1,540 @@
+//! Implementation of the min-max heap
+use crate::binary_heap::{Drain, Iter};
+use crate::invariant::InvariantError;
+use yos_memcursor::MemCursor;
+
+use alloc::vec::Vec;
+use core::fmt::Debug;
+use core::mem::swap;
+
+/// Implementation of the min-max heap, a double-ended priority queue.
+///
+/// The elements are kept in a vector with the Atkinson layout: the levels
+/// of the implicit binary tree alternate between min levels, starting with
+/// the root, and max levels. An element on a min level is smaller or equal
+/// than all its descendants, an element on a max level is greater or equal
+/// than all its descendants. The smallest element is the root and the
+/// greatest element is one of its children.
+pub struct MinMaxHeap<T> {
+    pub(crate) data: Vec<T>,
+}
+
+impl<T: Clone> Clone for MinMaxHeap<T> {
+    fn clone(&self) -> Self {
+        Self {
+            data: self.data.clone(),
+        }
+    }
+
+    fn clone_from(&mut self, source: &Self) {
+        self.data.clone_from(&source.data);
+    }
+}
+
+impl<T: Ord> Default for MinMaxHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Debug> Debug for MinMaxHeap<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+/// Returns true if the index is on a min level of the tree.
+#[inline]
+fn is_min_level(pos: usize) -> bool {
+    (usize::BITS - (pos + 1).leading_zeros() - 1) & 1 == 0
+}
+
+/// Returns true if `a` has to be closer to the root than `b`: smaller on the
+/// min levels and greater on the max levels.
+#[inline]
+fn before<T: Ord>(min: bool, a: &T, b: &T) -> bool {
+    if min {
+        a < b
+    } else {
+        a > b
+    }
+}
+
+impl<T: Ord> MinMaxHeap<T> {
+    /// Creates a new instance of the min-max heap.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::min_max_heap::MinMaxHeap;
+    ///
+    /// let mut heap = MinMaxHeap::new();
+    /// heap.push(1);
+    /// heap.push(3);
+    /// heap.push(2);
+    ///
+    /// assert_eq!(3, heap.len());
+    /// ```
+    pub fn new() -> Self {
+        Self { data: Vec::new() }
+    }
+
+    /// Creates a new instance of the min-max heap with a given capacity.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::min_max_heap::MinMaxHeap;
+    ///
+    /// let heap = MinMaxHeap::<u8>::with_capacity(10);
+    /// assert!(heap.capacity() >= 10);
+    /// ```
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            data: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Returns the greatest element in the min-max heap,
+    /// or `None` if it is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::min_max_heap::MinMaxHeap;
+    ///
+    /// let heap = MinMaxHeap::from(vec![3, 1, 5, 2]);
+    /// assert_eq!(Some(&5), heap.peek_max());
+    /// ```
+    pub fn peek_max(&self) -> Option<&T> {
+        self.max_pos().map(|pos| &self.data[pos])
+    }
+
+    /// Pushes a value into the min-max heap.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::min_max_heap::MinMaxHeap;
+    ///
+    /// let mut heap = MinMaxHeap::new();
+    /// heap.push(2);
+    /// heap.push(3);
+    /// heap.push(1);
+    ///
+    /// assert_eq!(Some(&1), heap.peek_min());
+    /// assert_eq!(Some(&3), heap.peek_max());
+    /// ```
+    pub fn push(&mut self, value: T) {
+        let old_len = self.len();
+        self.data.push(value);
+
+        self.bubble_up(old_len);
+    }
+
+    /// Removes the smallest item from the min-max heap and returns it,
+    /// or `None` if it is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::min_max_heap::MinMaxHeap;
+    ///
+    /// let mut heap = MinMaxHeap::from(vec![3, 1, 2]);
+    ///
+    /// assert_eq!(Some(1), heap.pop_min());
+    /// assert_eq!(Some(2), heap.pop_min());
+    /// ```
+    pub fn pop_min(&mut self) -> Option<T> {
+        self.remove_at(0)
+    }
+
+    /// Removes the greatest item from the min-max heap and returns it,
+    /// or `None` if it is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::min_max_heap::MinMaxHeap;
+    ///
+    /// let mut heap = MinMaxHeap::from(vec![3, 1, 2]);
+    ///
+    /// assert_eq!(Some(3), heap.pop_max());
+    /// assert_eq!(Some(2), heap.pop_max());
+    /// ```
+    pub fn pop_max(&mut self) -> Option<T> {
+        match self.max_pos() {
+            Some(pos) => self.remove_at(pos),
+            None => None,
+        }
+    }
+
+    /// Consumes the min-max heap and returns a vector with the elements
+    /// in ascending order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::min_max_heap::MinMaxHeap;
+    ///
+    /// let heap = MinMaxHeap::from(vec![4, 1, 5, 3, 2]);
+    /// assert_eq!(vec![1, 2, 3, 4, 5], heap.into_sorted_vec());
+    /// ```
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        let mut xs = Vec::with_capacity(self.len());
+        while let Some(x) = self.pop_min() {
+            xs.push(x);
+        }
+        xs
+    }
+
+    /// Checks that every element on a min level is smaller or equal, and
+    /// every element on a max level is greater or equal, than all its
+    /// descendants. The error names the indexes of the first element
+    /// which breaks the order and of its ancestor.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::min_max_heap::MinMaxHeap;
+    ///
+    /// let heap = MinMaxHeap::from(vec![1, 5, 2, 4, 3]);
+    /// assert!(heap.validate().is_ok());
+    /// ```
+    pub fn validate(&self) -> Result<(), InvariantError> {
+        for node in 1..self.len() {
+            let min = is_min_level(node);
+
+            // The parent is on the opposite kind of level.
+            let parent = (node - 1) / 2;
+            if before(!min, &self.data[node], &self.data[parent]) {
+                return Err(InvariantError::Level {
+                    ancestor: parent,
+                    node,
+                });
+            }
+
+            if parent > 0 {
+                let grandparent = (parent - 1) / 2;
+                if before(min, &self.data[node], &self.data[grandparent]) {
+                    return Err(InvariantError::Level {
+                        ancestor: grandparent,
+                        node,
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Rebuilds the whole heap, from scratch.
+    pub(crate) fn rebuild(&mut self) {
+        let mut n = self.len() / 2;
+        while n > 0 {
+            n -= 1;
+            self.trickle_down(n);
+        }
+    }
+
+    /// Returns the position of the greatest element.
+    fn max_pos(&self) -> Option<usize> {
+        match self.len() {
+            0 => None,
+            1 => Some(0),
+            2 => Some(1),
+            _ => Some(if self.data[1] >= self.data[2] { 1 } else { 2 }),
+        }
+    }
+
+    /// Removes the element at `pos`, replacing it with the last element.
+    fn remove_at(&mut self, pos: usize) -> Option<T> {
+        self.data.pop().map(|mut item| {
+            if pos < self.len() {
+                swap(&mut item, &mut self.data[pos]);
+                self.trickle_down(pos);
+            }
+
+            item
+        })
+    }
+
+    /// Bubbles up into the heap the value at 'pos'.
+    pub(crate) fn bubble_up(&mut self, pos: usize) {
+        if pos == 0 {
+            return;
+        }
+
+        let mut min = is_min_level(pos);
+
+        unsafe {
+            // take out the value at 'pos' and create a hole.
+            let mut mc = MemCursor::new(&mut self.data, pos);
+
+            // When the value belongs to the levels of the other kind,
+            // move it to its parent first.
+            let parent = (pos - 1) / 2;
+            if before(!min, mc.element(), mc.get(parent)) {
+                mc.move_to(parent);
+                min = !min;
+            }
+
+            // Then climb the levels of the same kind.
+            while mc.pos() > 2 {
+                let grandparent = ((mc.pos() - 1) / 2 - 1) / 2;
+                if !before(min, mc.element(), mc.get(grandparent)) {
+                    break;
+                }
+
+                mc.move_to(grandparent);
+            }
+        }
+    }
+
+    /// Trickles down into the heap the value at 'pos'.
+    fn trickle_down(&mut self, mut pos: usize) {
+        let min = is_min_level(pos);
+        let end = self.len();
+
+        loop {
+            // The position where the value is swapped with its parent
+            // before trickling down again.
+            let mut again = None;
+
+            unsafe {
+                let mut mc = MemCursor::new(&mut self.data, pos);
+
+                loop {
+                    let child = 2 * mc.pos() + 1;
+                    if child >= end {
+                        break;
+                    }
+
+                    // find the first of the children and grandchildren.
+                    let grandchild = 2 * child + 1;
+                    let mut m = child;
+                    for d in [
+                        child + 1,
+                        grandchild,
+                        grandchild + 1,
+                        grandchild + 2,
+                        grandchild + 3,
+                    ] {
+                        if d < end && before(min, mc.get(d), mc.get(m)) {
+                            m = d;
+                        }
+                    }
+
+                    if !before(min, mc.get(m), mc.element()) {
+                        break;
+                    }
+
+                    mc.move_to(m);
+
+                    if m < grandchild {
+                        // A child has no children of the same kind of level.
+                        break;
+                    }
+
+                    let parent = (m - 1) / 2;
+                    if before(min, mc.get(parent), mc.element()) {
+                        again = Some((m, parent));
+                        break;
+                    }
+                }
+            }
+
+            match again {
+                Some((m, parent)) => {
+                    self.data.swap(m, parent);
+                    pos = m;
+                }
+                None => break,
+            }
+        }
+    }
+}
+
+impl<T> MinMaxHeap<T> {
+    /// Returns an iterator that is visiting all values in the min-max heap.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter::new(self.data.iter())
+    }
+
+    /// Returns the smallest element in the min-max heap,
+    /// or `None` if it is empty.
+    pub fn peek_min(&self) -> Option<&T> {
+        self.data.first()
+    }
+
+    /// Returns a slice with all the elements in the min-max heap,
+    /// in the order they are stored.
+    pub fn as_slice(&self) -> &[T] {
+        self.data.as_slice()
+    }
+
+    /// Removes all the elements from the min-max heap and returns them
+    /// in an arbitrary order. The allocated memory is kept.
+    pub fn drain(&mut self) -> Drain<'_, T> {
+        Drain::new(self.data.drain(..))
+    }
+
+    /// Removes all the elements from the min-max heap.
+    pub fn clear(&mut self) {
+        self.data.clear();
+    }
+
+    /// Returns the number of elements the min-max heap can hold.
+    pub fn capacity(&self) -> usize {
+        self.data.capacity()
+    }
+
+    /// Reserves the minimum capacity for additional more elements
+    /// to be inserted in the min-max heap.
+    pub fn reserve_exact(&mut self, additional: usize) {
+        self.data.reserve_exact(additional);
+    }
+
+    /// Reserves capacity for at least additional more elements
+    /// to be inserted in the min-max heap.
+    pub fn reserve(&mut self, additional: usize) {
+        self.data.reserve(additional);
+    }
+
+    /// Shrinks the allocated memory.
+    pub fn shrink_to_fit(&mut self) {
+        self.data.shrink_to_fit();
+    }
+
+    /// Returns the number of elements in the min-max heap.
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns a flag indicating whenever the min-max heap is empty.
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::lcg;
+
+    #[test]
+    fn levels_pass() {
+        let levels: Vec<bool> = (0..8).map(is_min_level).collect();
+        assert_eq!(
+            vec![true, false, false, true, true, true, true, false],
+            levels
+        );
+    }
+
+    #[test]
+    fn new_pass() {
+        let h = MinMaxHeap::<u8>::new();
+        assert!(h.is_empty());
+        assert_eq!(None, h.peek_min());
+        assert_eq!(None, h.peek_max());
+    }
+
+    #[test]
+    fn debug_pass() {
+        let h = MinMaxHeap::from(vec![1]);
+        assert_eq!("[1]", format!("{:?}", h));
+    }
+
+    #[test]
+    fn push_pass() {
+        let mut seed = 1u64;
+        let mut h = MinMaxHeap::new();
+        for _ in 0..500 {
+            h.push(lcg(&mut seed) % 100);
+            assert_eq!(Ok(()), h.validate());
+            assert_eq!(h.iter().min(), h.peek_min());
+            assert_eq!(h.iter().max(), h.peek_max());
+        }
+    }
+
+    #[test]
+    fn pop_min_max_pass() {
+        let mut seed = 2u64;
+        let xs: Vec<u64> = (0..1000).map(|_| lcg(&mut seed) % 300).collect();
+        let mut h = MinMaxHeap::from(xs.clone());
+        assert_eq!(Ok(()), h.validate());
+
+        let mut ys = xs;
+        ys.sort_unstable();
+        let mut lo = 0;
+        let mut hi = ys.len();
+
+        while lo < hi {
+            if lcg(&mut seed) & 1 == 0 {
+                assert_eq!(Some(ys[lo]), h.pop_min());
+                lo += 1;
+            } else {
+                hi -= 1;
+                assert_eq!(Some(ys[hi]), h.pop_max());
+            }
+            assert_eq!(Ok(()), h.validate());
+        }
+
+        assert_eq!(None, h.pop_min());
+        assert_eq!(None, h.pop_max());
+    }
+
+    #[test]
+    fn small_pass() {
+        let mut h = MinMaxHeap::new();
+        h.push(2);
+        assert_eq!(Some(&2), h.peek_max());
+        h.push(1);
+        assert_eq!(Some(&2), h.peek_max());
+        assert_eq!(Some(&1), h.peek_min());
+        assert_eq!(Some(2), h.pop_max());
+        assert_eq!(Some(1), h.pop_max());
+    }
+
+    #[test]
+    fn validate_fail() {
+        let h = MinMaxHeap {
+            data: vec![1, 5, 4, 6],
+        };
+        assert_eq!(
+            Err(InvariantError::Level {
+                ancestor: 1,
+                node: 3
+            }),
+            h.validate()
+        );
+
+        let h = MinMaxHeap {
+            data: vec![2, 5, 4, 3, 1],
+        };
+        assert_eq!(
+            Err(InvariantError::Level {
+                ancestor: 0,
+                node: 4
+            }),
+            h.validate()
+        );
+    }
+
+    #[test]
+    fn into_sorted_vec_pass() {
+        let h = MinMaxHeap::from(vec![5, 3, 9, 1, 7]);
+        assert_eq!(vec![1, 3, 5, 7, 9], h.into_sorted_vec());
+    }
+
+    #[test]
+    fn drain_clear_pass() {
+        let mut h = MinMaxHeap::from(vec![4, 2, 3]);
+        assert_eq!(3, h.drain().count());
+        assert!(h.is_empty());
+
+        h.push(1);
+        h.clear();
+        assert!(h.is_empty());
+    }
+}
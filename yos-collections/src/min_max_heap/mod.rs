@@ -0,0 +1,7 @@
+//! Min-max heap
+mod convert;
+mod iter;
+mod main;
+
+pub use crate::binary_heap::{Drain, IntoIter, Iter};
+pub use crate::min_max_heap::main::MinMaxHeap;
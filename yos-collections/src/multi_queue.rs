@@ -0,0 +1,464 @@
+//! Concurrent, relaxed priority queue made of sharded binary heaps.
+use crate::binary_heap::BinaryHeap;
+use crate::compare::{Compare, FnComparator, KeyComparator, MaxComparator, MinComparator};
+
+use std::cell::Cell;
+use std::cmp::Ordering;
+use std::collections::hash_map::RandomState;
+use std::fmt::Debug;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
+use std::sync::{Mutex, MutexGuard, PoisonError, TryLockError};
+
+/// How many times a push or a pop looks for unlocked shards before it
+/// waits for a lock.
+const ATTEMPTS: usize = 8;
+
+thread_local! {
+    /// The state of the xorshift generator of the current thread.
+    static SEED: Cell<u64> = Cell::new(RandomState::new().build_hasher().finish() | 1);
+}
+
+/// Returns a random number in `0..n` from the generator of the current thread.
+fn random(n: usize) -> usize {
+    SEED.with(|seed| {
+        let mut x = seed.get();
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        seed.set(x);
+        (x % n as u64) as usize
+    })
+}
+
+/// A priority queue which can be shared between threads, a MultiQueue.
+///
+/// The elements are spread over several shards, each one a `BinaryHeap`
+/// behind its own `Mutex`. A push goes to a random unlocked shard. A pop
+/// looks at the tops of two random unlocked shards and removes the better
+/// one, so the threads rarely wait for each other.
+///
+/// The price is a relaxed order: the popped element is the top of its
+/// shard, but not always the top of the whole queue. With `n` shards the
+/// rank of a popped element, the number of elements in the queue which
+/// come before it, is O(n) in expectation and O(n log n) with high
+/// probability (Alistarh et al., *The Power of Choice in Priority
+/// Scheduling*). A queue with a single shard is a strict priority queue.
+///
+/// The queue is `Send` and `Sync` when the elements are `Send` and the
+/// comparator is `Send` and `Sync`, so it can be shared through an `Arc`
+/// or by scoped threads.
+pub struct MultiQueue<T, C = MaxComparator> {
+    shards: Vec<Mutex<BinaryHeap<T, C>>>,
+    cmp: C,
+    len: AtomicUsize,
+}
+
+impl<T, C> Debug for MultiQueue<T, C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MultiQueue")
+            .field("shards", &self.shards.len())
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+impl<T: Ord> MultiQueue<T> {
+    /// Creates a new instance of a max-queue with the given number of shards.
+    /// About twice the number of threads sharing the queue is a good start.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of shards is zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::multi_queue::MultiQueue;
+    ///
+    /// let queue = MultiQueue::new(1);
+    /// queue.push(3);
+    /// queue.push(7);
+    ///
+    /// assert_eq!(Some(7), queue.pop());
+    /// ```
+    pub fn new(shards: usize) -> Self {
+        Self::from_comparator(shards, MaxComparator)
+    }
+}
+
+impl<T: Ord> MultiQueue<T, MinComparator> {
+    /// Creates a new instance of a min-queue with the given number of shards.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::multi_queue::MultiQueue;
+    ///
+    /// let queue = MultiQueue::new_min(1);
+    /// queue.push(3);
+    /// queue.push(7);
+    ///
+    /// assert_eq!(Some(3), queue.pop());
+    /// ```
+    pub fn new_min(shards: usize) -> Self {
+        Self::from_comparator(shards, MinComparator)
+    }
+}
+
+impl<T, F> MultiQueue<T, FnComparator<F>>
+where
+    F: Fn(&T, &T) -> Ordering + Clone,
+{
+    /// Creates a new instance which orders the elements with a closure.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::multi_queue::MultiQueue;
+    ///
+    /// let queue = MultiQueue::with_comparator(1, |a: &i32, b: &i32| a.abs().cmp(&b.abs()));
+    /// queue.push(-7);
+    /// queue.push(5);
+    ///
+    /// assert_eq!(Some(-7), queue.pop());
+    /// ```
+    pub fn with_comparator(shards: usize, f: F) -> Self {
+        Self::from_comparator(shards, FnComparator(f))
+    }
+}
+
+impl<T, K, F> MultiQueue<T, KeyComparator<F>>
+where
+    K: Ord,
+    F: Fn(&T) -> K + Clone,
+{
+    /// Creates a new instance which orders the elements by the keys
+    /// extracted with a closure.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::multi_queue::MultiQueue;
+    ///
+    /// let queue = MultiQueue::with_key(1, |x: &(&str, u32)| x.1);
+    /// queue.push(("low", 1));
+    /// queue.push(("high", 9));
+    ///
+    /// assert_eq!(Some(("high", 9)), queue.pop());
+    /// ```
+    pub fn with_key(shards: usize, f: F) -> Self {
+        Self::from_comparator(shards, KeyComparator(f))
+    }
+}
+
+impl<T, C: Compare<T> + Clone> MultiQueue<T, C> {
+    /// Creates a new instance with the given number of shards, which orders
+    /// the elements with the given comparator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of shards is zero.
+    pub fn from_comparator(shards: usize, cmp: C) -> Self {
+        assert!(shards > 0, "the queue needs at least one shard");
+
+        Self {
+            shards: (0..shards)
+                .map(|_| Mutex::new(BinaryHeap::from_comparator(cmp.clone())))
+                .collect(),
+            cmp,
+            len: AtomicUsize::new(0),
+        }
+    }
+
+    /// Pushes an element into a random shard.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::thread;
+    /// use yos_collections::multi_queue::MultiQueue;
+    ///
+    /// let queue = MultiQueue::new(4);
+    /// thread::scope(|s| {
+    ///     for t in 0..4 {
+    ///         let queue = &queue;
+    ///         s.spawn(move || (0..100).for_each(|x| queue.push(t * 100 + x)));
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(400, queue.len());
+    /// ```
+    pub fn push(&self, item: T) {
+        let mut shard = (0..ATTEMPTS)
+            .find_map(|_| self.try_lock(random(self.shards.len())))
+            .unwrap_or_else(|| self.lock(random(self.shards.len())));
+
+        shard.push(item);
+        self.len.fetch_add(1, AtomicOrdering::Relaxed);
+    }
+
+    /// Removes an element with a high priority and returns it, or `None`
+    /// if the queue is empty. The element is the better of the tops of two
+    /// random shards, see the relaxation bound of the queue.
+    ///
+    /// When the two shards are empty or locked too many times in a row, the
+    /// pop locks all the shards and removes the top of the whole queue.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::multi_queue::MultiQueue;
+    ///
+    /// let mut queue = MultiQueue::new(4);
+    /// queue.extend(0..100);
+    ///
+    /// let mut xs: Vec<_> = std::iter::from_fn(|| queue.pop()).collect();
+    /// xs.sort();
+    ///
+    /// assert_eq!((0..100).collect::<Vec<_>>(), xs);
+    /// assert!(queue.is_empty());
+    /// ```
+    pub fn pop(&self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let n = self.shards.len();
+        if n > 1 {
+            for _ in 0..ATTEMPTS {
+                // Two distinct shards.
+                let i = random(n);
+                let j = (i + 1 + random(n - 1)) % n;
+
+                let (a, b) = match (self.try_lock(i), self.try_lock(j)) {
+                    (Some(a), Some(b)) => (a, b),
+                    _ => continue,
+                };
+
+                let first = match (a.peek(), b.peek()) {
+                    (Some(x), Some(y)) => self.cmp.compare(x, y) != Ordering::Less,
+                    (Some(_), None) => true,
+                    (None, Some(_)) => false,
+                    (None, None) => continue,
+                };
+
+                let mut shard = if first { a } else { b };
+                let item = shard.pop();
+                self.len.fetch_sub(1, AtomicOrdering::Relaxed);
+                return item;
+            }
+        }
+
+        self.pop_strict()
+    }
+
+    /// Locks all the shards, in order, and removes the top of the queue.
+    fn pop_strict(&self) -> Option<T> {
+        let mut shards: Vec<_> = (0..self.shards.len()).map(|i| self.lock(i)).collect();
+
+        let mut best: Option<usize> = None;
+        for (i, shard) in shards.iter().enumerate() {
+            if let Some(x) = shard.peek() {
+                best = match best.and_then(|b| shards[b].peek()) {
+                    Some(y) if self.cmp.compare(x, y) != Ordering::Greater => best,
+                    _ => Some(i),
+                };
+            }
+        }
+
+        best.and_then(|i| {
+            let item = shards[i].pop();
+            self.len.fetch_sub(1, AtomicOrdering::Relaxed);
+            item
+        })
+    }
+
+    /// Consumes the queue and returns its elements in sorted order,
+    /// ascending with respect to the comparator.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::multi_queue::MultiQueue;
+    ///
+    /// let mut queue = MultiQueue::new(3);
+    /// queue.extend(vec![5, 1, 4, 2, 3]);
+    ///
+    /// assert_eq!(vec![1, 2, 3, 4, 5], queue.into_sorted_vec());
+    /// ```
+    pub fn into_sorted_vec(self) -> Vec<T> {
+        let mut heap = BinaryHeap::from_comparator(self.cmp);
+        for shard in self.shards {
+            let mut shard = shard.into_inner().unwrap_or_else(PoisonError::into_inner);
+            heap.append(&mut shard);
+        }
+        heap.into_sorted_vec()
+    }
+}
+
+impl<T, C> MultiQueue<T, C> {
+    /// Returns the number of shards of the queue.
+    pub fn shards(&self) -> usize {
+        self.shards.len()
+    }
+
+    /// Returns the number of elements in the queue. While other threads
+    /// push and pop, the number may be outdated as soon as it is returned.
+    pub fn len(&self) -> usize {
+        self.len.load(AtomicOrdering::Relaxed)
+    }
+
+    /// Returns a flag indicating whenever the queue is empty. While other
+    /// threads push and pop, the flag may be outdated as soon as it is
+    /// returned.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // A shard is poisoned when a comparator panics. The binary heap keeps
+    // all its elements in that case, so the shard can still be used.
+
+    fn lock(&self, i: usize) -> MutexGuard<'_, BinaryHeap<T, C>> {
+        self.shards[i]
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn try_lock(&self, i: usize) -> Option<MutexGuard<'_, BinaryHeap<T, C>>> {
+        match self.shards[i].try_lock() {
+            Ok(shard) => Some(shard),
+            Err(TryLockError::Poisoned(e)) => Some(e.into_inner()),
+            Err(TryLockError::WouldBlock) => None,
+        }
+    }
+}
+
+impl<T, C: Compare<T> + Clone> Extend<T> for MultiQueue<T, C> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|x| self.push(x));
+    }
+}
+
+/// A shared queue can be extended from several threads at once.
+impl<T, C: Compare<T> + Clone> Extend<T> for &MultiQueue<T, C> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|x| self.push(x));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::thread;
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn send_sync_pass() {
+        assert_send_sync::<MultiQueue<String>>();
+        assert_send_sync::<MultiQueue<u32, MinComparator>>();
+    }
+
+    #[test]
+    fn strict_pass() {
+        let queue = MultiQueue::new(1);
+        (&queue).extend(vec![3, 9, 1, 7]);
+
+        assert_eq!(Some(9), queue.pop());
+        assert_eq!(Some(7), queue.pop());
+        assert_eq!(Some(3), queue.pop());
+        assert_eq!(Some(1), queue.pop());
+        assert_eq!(None, queue.pop());
+    }
+
+    #[test]
+    fn empty_pass() {
+        let queue = MultiQueue::<u8>::new(8);
+        assert!(queue.is_empty());
+        assert_eq!(None, queue.pop());
+        assert_eq!(8, queue.shards());
+    }
+
+    #[test]
+    fn relaxation_pass() {
+        // A single thread pops all the elements and counts, for every pop,
+        // how many of the remaining elements were better.
+        let shards = 8;
+        let len = 20_000;
+        let queue = MultiQueue::new_min(shards);
+        (&queue).extend(0..len);
+
+        let mut popped = vec![false; len];
+        let mut smallest = 0;
+        let mut total = 0;
+        while let Some(x) = queue.pop() {
+            popped[x] = true;
+            total += popped[smallest..x].iter().filter(|p| !**p).count();
+            while smallest < len && popped[smallest] {
+                smallest += 1;
+            }
+        }
+
+        assert_eq!(len, smallest);
+        assert!(total / len <= 2 * shards, "mean rank {}", total / len);
+    }
+
+    #[test]
+    fn stress_pass() {
+        // Producers and consumers at the same time, every element has to
+        // come out exactly once.
+        let threads = 8;
+        let per_thread = 5_000;
+        let queue = Arc::new(MultiQueue::new(2 * threads));
+
+        let producers: Vec<_> = (0..threads)
+            .map(|t| {
+                let queue = Arc::clone(&queue);
+                thread::spawn(move || {
+                    (0..per_thread).for_each(|x| queue.push(t * per_thread + x));
+                })
+            })
+            .collect();
+
+        let consumers: Vec<_> = (0..threads)
+            .map(|_| {
+                let queue = Arc::clone(&queue);
+                thread::spawn(move || {
+                    let mut xs = Vec::new();
+                    for _ in 0..per_thread / 2 {
+                        if let Some(x) = queue.pop() {
+                            xs.push(x);
+                        }
+                    }
+                    xs
+                })
+            })
+            .collect();
+
+        producers.into_iter().for_each(|p| p.join().unwrap());
+        let mut xs: Vec<usize> = consumers
+            .into_iter()
+            .flat_map(|c| c.join().unwrap())
+            .collect();
+        xs.extend(std::iter::from_fn(|| queue.pop()));
+
+        assert!(queue.is_empty());
+        xs.sort_unstable();
+        assert_eq!((0..threads * per_thread).collect::<Vec<_>>(), xs);
+    }
+
+    #[test]
+    fn into_sorted_vec_pass() {
+        let queue = MultiQueue::with_key(4, |x: &(u32, char)| x.0);
+        (&queue).extend(vec![(3, 'c'), (1, 'a'), (2, 'b')]);
+        assert_eq!(vec![(1, 'a'), (2, 'b'), (3, 'c')], queue.into_sorted_vec());
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one shard")]
+    fn shards_fail() {
+        MultiQueue::<u8>::new(0);
+    }
+}
@@ -0,0 +1,4 @@
+//! Conversions from and into a pairing heap.
+use super::{IntoIter, Iter, PairingHeap};
+
+crate::handle_heap::conversions!(PairingHeap);
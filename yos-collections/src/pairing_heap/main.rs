@@ -0,0 +1,806 @@
+//! Implementation of the pairing heap
+use super::iter::*;
+use crate::arena::Handle;
+use crate::compare::{Compare, FnComparator, KeyComparator, MaxComparator, MinComparator};
+use crate::invariant::InvariantError;
+use crate::pool::{Key, Pool, Rename};
+
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt::Debug;
+use core::mem;
+
+/// A node of the pairing heap. The nodes are stored in a pool and are
+/// linked through their keys.
+#[derive(Clone)]
+pub(crate) struct Slot<T> {
+    /// The stored value.
+    pub(crate) item: T,
+    /// The parent for the first child, otherwise the previous sibling.
+    /// The root doesn't have a previous node.
+    prev: Option<Key>,
+    /// Optional next sibling.
+    sibling: Option<Key>,
+    /// Optional first child.
+    child: Option<Key>,
+}
+
+impl<T> Slot<T> {
+    /// Replaces the links of a cloned node by the keys of the cloned nodes.
+    fn relink(&mut self, rename: &Rename) {
+        self.prev = rename.link(self.prev);
+        self.sibling = rename.link(self.sibling);
+        self.child = rename.link(self.child);
+    }
+}
+
+/// A node which is being visited by `validate`: its key, the parent and
+/// its position, and the node which should be linked as the previous one.
+type Visit = (Key, Option<(Key, usize)>, Option<Key>);
+
+/// Implementation of the pairing heap, a single tree where the children of
+/// a node are kept in a list.
+///
+/// The push, the meld and the key updates which move an element towards
+/// the top take `O(1)` time, the pop pairs the children of the root in two
+/// passes and takes `O(log n)` amortized time. The order of the elements is
+/// given by the comparator `C`, and so is the direction of the key updates:
+/// `increase_key` moves an element towards the top, `decrease_key` moves it
+/// away from the top. In a min-heap, created with `new_min`, lowering a value
+/// is an `increase_key`. By default the heap is a max-heap, which keeps the
+/// greatest element at the top.
+///
+/// The nodes live in arenas. A meld takes over the arenas of the other
+/// heap without moving its nodes, see `append`.
+pub struct PairingHeap<T, C = MaxComparator> {
+    /// The nodes of the heap.
+    pub(crate) nodes: Pool<Slot<T>>,
+    /// The root of the tree.
+    root: Option<Key>,
+    cmp: C,
+}
+
+impl<T: Clone, C: Clone> Clone for PairingHeap<T, C> {
+    fn clone(&self) -> Self {
+        let (mut nodes, rename) = self.nodes.clone_renamed();
+        nodes.values_mut().for_each(|slot| slot.relink(&rename));
+
+        Self {
+            nodes,
+            root: rename.link(self.root),
+            cmp: self.cmp.clone(),
+        }
+    }
+}
+
+impl<T, C: Compare<T> + Default> Default for PairingHeap<T, C> {
+    fn default() -> Self {
+        Self::from_comparator(C::default())
+    }
+}
+
+impl<T: Debug, C> Debug for PairingHeap<T, C> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: Ord> PairingHeap<T> {
+    /// Creates a new instance of the pairing heap.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::pairing_heap::PairingHeap;
+    ///
+    /// let mut heap = PairingHeap::new();
+    /// heap.push(1);
+    /// heap.push(3);
+    /// heap.push(2);
+    ///
+    /// assert_eq!(3, heap.len());
+    /// ```
+    pub fn new() -> Self {
+        Self::from_comparator(MaxComparator)
+    }
+
+    /// Creates a new instance of the pairing heap with a given capacity.
+    /// Pushing up to `capacity` elements does not reallocate the storage.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            nodes: Pool::with_capacity(capacity),
+            root: None,
+            cmp: MaxComparator,
+        }
+    }
+}
+
+impl<T: Ord> PairingHeap<T, MinComparator> {
+    /// Creates a new instance of a min-heap, which keeps the smallest
+    /// element at the top. In a min-heap a smaller value moves the element
+    /// towards the top, so it is set by `increase_key` in `O(1)` time.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::pairing_heap::PairingHeap;
+    ///
+    /// let mut heap = PairingHeap::new_min();
+    /// heap.push(3);
+    /// let h = heap.push(5);
+    ///
+    /// heap.increase_key(h, 1);
+    /// assert_eq!(Some(1), heap.pop());
+    /// ```
+    pub fn new_min() -> Self {
+        Self::from_comparator(MinComparator)
+    }
+}
+
+impl<T, F> PairingHeap<T, FnComparator<F>>
+where
+    F: Fn(&T, &T) -> Ordering,
+{
+    /// Creates a new instance of the pairing heap which orders the elements
+    /// with a closure. The greatest element, according to the closure,
+    /// is kept at the top.
+    pub fn with_comparator(f: F) -> Self {
+        Self::from_comparator(FnComparator(f))
+    }
+}
+
+impl<T, K, F> PairingHeap<T, KeyComparator<F>>
+where
+    K: Ord,
+    F: Fn(&T) -> K,
+{
+    /// Creates a new instance of the pairing heap which orders the elements
+    /// by a key extracted with a closure. The element with the greatest key
+    /// is kept at the top.
+    pub fn with_key(f: F) -> Self {
+        Self::from_comparator(KeyComparator(f))
+    }
+}
+
+impl<T, C: Compare<T>> PairingHeap<T, C> {
+    /// Creates a new instance of the pairing heap which orders
+    /// the elements with the given comparator.
+    pub fn from_comparator(cmp: C) -> Self {
+        Self {
+            nodes: Pool::new(),
+            root: None,
+            cmp,
+        }
+    }
+
+    /// Pushes a value into the pairing heap and returns its handle.
+    /// The value is melded with the root in `O(1)` time.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::pairing_heap::PairingHeap;
+    ///
+    /// let mut heap = PairingHeap::new();
+    /// heap.push(1);
+    /// let h = heap.push(3);
+    ///
+    /// assert_eq!(Some(&3), heap.get(h));
+    /// assert_eq!(Some(&3), heap.peek());
+    /// ```
+    pub fn push(&mut self, item: T) -> Handle {
+        let x = self.nodes.insert(Slot {
+            item,
+            prev: None,
+            sibling: None,
+            child: None,
+        });
+
+        self.add_tree(x);
+        self.nodes.handle(x)
+    }
+
+    /// Removes the greatest item from the pairing heap and returns it,
+    /// or `None` if it is empty. The children of the root are paired
+    /// from left to right and then melded from right to left.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::pairing_heap::PairingHeap;
+    ///
+    /// let mut heap = PairingHeap::new();
+    /// assert_eq!(None, heap.pop());
+    ///
+    /// heap.push(1);
+    /// heap.push(3);
+    /// heap.push(2);
+    ///
+    /// assert_eq!(Some(3), heap.pop());
+    /// assert_eq!(Some(2), heap.pop());
+    /// ```
+    pub fn pop(&mut self) -> Option<T> {
+        let r = self.root?;
+
+        self.detach(r);
+        Some(self.nodes.remove(r).item)
+    }
+
+    /// Moves all the elements of `other` into `self`, leaving `other` empty.
+    ///
+    /// The storage of `other` is taken over without moving its nodes and
+    /// the roots are melded in `O(1)` time. The handles of both heaps stay
+    /// valid and name their elements in `self`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::pairing_heap::PairingHeap;
+    ///
+    /// let mut a = PairingHeap::new();
+    /// let g = a.push(1);
+    /// a.push(5);
+    /// let mut b = PairingHeap::new();
+    /// let h = b.push(3);
+    /// b.push(7);
+    /// b.push(2);
+    ///
+    /// a.append(&mut b);
+    ///
+    /// assert_eq!(5, a.len());
+    /// assert!(b.is_empty());
+    /// assert_eq!(Some(&7), a.peek());
+    /// assert_eq!(Some(&1), a.get(g));
+    /// assert_eq!(Some(&3), a.get(h));
+    /// ```
+    pub fn append(&mut self, other: &mut Self) {
+        self.nodes.append(&mut other.nodes);
+        if let Some(r) = other.root.take() {
+            self.add_tree(r);
+        }
+    }
+
+    /// Replaces the value of the element with the given handle by a value
+    /// which the comparator orders greater or equal than the current one,
+    /// so the element moves towards the top. Its tree is cut and melded
+    /// with the root in `O(1)` time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the handle is not in the heap or if the comparator orders
+    /// the new value below the current one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::pairing_heap::PairingHeap;
+    ///
+    /// let mut heap = PairingHeap::new();
+    /// heap.push(5);
+    /// let h = heap.push(1);
+    ///
+    /// heap.increase_key(h, 10);
+    /// assert_eq!(Some(&10), heap.peek());
+    /// ```
+    pub fn increase_key(&mut self, handle: Handle, item: T) {
+        let current = self.get(handle).expect("the handle is not in the heap");
+        assert!(
+            self.cmp.compare(&item, current).is_ge(),
+            "the new value is ordered below the current one"
+        );
+
+        self.update_key(handle, item);
+    }
+
+    /// Replaces the value of the element with the given handle by a value
+    /// which the comparator orders smaller or equal than the current one,
+    /// so the element moves away from the top. The node is taken out of the
+    /// heap and pushed back in `O(log n)` amortized time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the handle is not in the heap or if the comparator orders
+    /// the new value above the current one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::pairing_heap::PairingHeap;
+    ///
+    /// let mut heap = PairingHeap::new();
+    /// let h = heap.push(10);
+    /// heap.push(5);
+    ///
+    /// heap.decrease_key(h, 1);
+    /// assert_eq!(Some(&5), heap.peek());
+    /// assert_eq!(Some(&1), heap.get(h));
+    /// ```
+    pub fn decrease_key(&mut self, handle: Handle, item: T) {
+        let current = self.get(handle).expect("the handle is not in the heap");
+        assert!(
+            self.cmp.compare(&item, current).is_le(),
+            "the new value is ordered above the current one"
+        );
+
+        self.update_key(handle, item);
+    }
+
+    /// Replaces the value of the element with the given handle and returns
+    /// the old value. When the element moves towards the top its tree is cut
+    /// and melded with the root in `O(1)` time, otherwise the element is taken
+    /// out of the heap and pushed back in `O(log n)` amortized time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the handle is not in the heap.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::pairing_heap::PairingHeap;
+    ///
+    /// let mut heap = PairingHeap::with_key(|x: &(char, u32)| x.1);
+    /// heap.push(('a', 5));
+    /// let h = heap.push(('b', 1));
+    ///
+    /// assert_eq!(('b', 1), heap.update_key(h, ('b', 10)));
+    /// assert_eq!(Some(&('b', 10)), heap.peek());
+    /// ```
+    pub fn update_key(&mut self, handle: Handle, item: T) -> T {
+        let x = self
+            .nodes
+            .key(handle)
+            .expect("the handle is not in the heap");
+        let old = mem::replace(&mut self.slot_mut(x).item, item);
+
+        if self.cmp.compare(&self.slot(x).item, &old).is_ge() {
+            if self.root != Some(x) {
+                self.cut(x);
+                self.add_tree(x);
+            }
+        } else {
+            // The children may be greater than the new value,
+            // take the node out and add it back as a single node.
+            self.detach(x);
+            self.add_tree(x);
+        }
+
+        old
+    }
+
+    /// Removes the element with the given handle from the pairing heap
+    /// and returns it, or `None` if the handle is not in the heap.
+    /// The operation takes `O(log n)` amortized time.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::pairing_heap::PairingHeap;
+    ///
+    /// let mut heap = PairingHeap::new();
+    /// heap.push(1);
+    /// let h = heap.push(3);
+    /// heap.push(2);
+    ///
+    /// assert_eq!(Some(3), heap.remove(h));
+    /// assert_eq!(None, heap.remove(h));
+    /// assert_eq!(Some(2), heap.pop());
+    /// ```
+    pub fn remove(&mut self, handle: Handle) -> Option<T> {
+        let x = self.nodes.key(handle)?;
+        self.detach(x);
+        Some(self.nodes.remove(x).item)
+    }
+
+    /// Checks the invariants of the pairing heap: every child is smaller or
+    /// equal than its parent, every node links back to its parent or to its
+    /// previous sibling, and every element is reachable from the root.
+    /// The error names the nodes by their depth-first position, each node
+    /// before its children and the children before the next sibling.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::pairing_heap::PairingHeap;
+    ///
+    /// let mut heap: PairingHeap<_> = vec![1, 5, 2, 4, 3].into_iter().collect();
+    /// heap.pop();
+    /// assert!(heap.validate().is_ok());
+    /// ```
+    pub fn validate(&self) -> Result<(), InvariantError> {
+        let mut position = 0;
+
+        if let Some(r) = self.root {
+            if self.slot(r).prev.is_some() || self.slot(r).sibling.is_some() {
+                return Err(InvariantError::Parent { node: 0 });
+            }
+        }
+
+        let mut stack: Vec<Visit> = self.root.map(|r| (r, None, None)).into_iter().collect();
+
+        while let Some((x, parent, prev)) = stack.pop() {
+            let id = position;
+            position += 1;
+            if position > self.nodes.len() {
+                // A broken list may link back to a visited node.
+                return Err(InvariantError::Length {
+                    expected: self.nodes.len(),
+                    found: position,
+                });
+            }
+
+            let slot = self.slot(x);
+            if slot.prev != prev {
+                return Err(InvariantError::Sibling { node: id });
+            }
+
+            if let Some((p, parent)) = parent {
+                if self.greater(x, p) {
+                    return Err(InvariantError::Order { parent, child: id });
+                }
+            }
+
+            // The next sibling comes after the children of the node.
+            if let Some(s) = slot.sibling {
+                stack.push((s, parent, Some(x)));
+            }
+            if let Some(c) = slot.child {
+                stack.push((c, Some((x, id)), Some(x)));
+            }
+        }
+
+        if position != self.nodes.len() {
+            return Err(InvariantError::Length {
+                expected: self.nodes.len(),
+                found: position,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Returns true if the value at `a` is greater than the value at `b`.
+    fn greater(&self, a: Key, b: Key) -> bool {
+        self.cmp
+            .compare(&self.slot(a).item, &self.slot(b).item)
+            .is_gt()
+    }
+
+    /// Melds the tree rooted in `x` with the root of the heap.
+    fn add_tree(&mut self, x: Key) {
+        self.root = Some(match self.root {
+            None => x,
+            Some(r) => self.meld(r, x),
+        });
+    }
+
+    /// Melds two trees, the root with the smaller value becomes the first
+    /// child of the other root. Returns the root of the melded tree.
+    fn meld(&mut self, a: Key, b: Key) -> Key {
+        let (parent, child) = if self.greater(b, a) { (b, a) } else { (a, b) };
+
+        let first = self.slot(parent).child;
+        if let Some(f) = first {
+            self.slot_mut(f).prev = Some(child);
+        }
+
+        let slot = self.slot_mut(child);
+        slot.prev = Some(parent);
+        slot.sibling = first;
+
+        self.slot_mut(parent).child = Some(child);
+        parent
+    }
+
+    /// Cuts the tree rooted in `x`, which is not the root of the heap,
+    /// from its parent.
+    fn cut(&mut self, x: Key) {
+        let prev = self.slot(x).prev.expect("the node is not the root");
+        let sibling = self.slot(x).sibling;
+
+        if self.slot(prev).child == Some(x) {
+            self.slot_mut(prev).child = sibling;
+        } else {
+            self.slot_mut(prev).sibling = sibling;
+        }
+
+        if let Some(s) = sibling {
+            self.slot_mut(s).prev = Some(prev);
+        }
+
+        let slot = self.slot_mut(x);
+        slot.prev = None;
+        slot.sibling = None;
+    }
+
+    /// Takes the node out of the heap. Its children are paired and melded
+    /// back into the heap. The node stays in the pool as a single node
+    /// which is not linked to the heap.
+    fn detach(&mut self, x: Key) {
+        if self.root == Some(x) {
+            self.root = None;
+        } else {
+            self.cut(x);
+        }
+
+        let child = self.slot_mut(x).child.take();
+        if let Some(tree) = self.pair(child) {
+            self.add_tree(tree);
+        }
+    }
+
+    /// Melds a list of siblings into a single tree in two passes: the trees
+    /// are melded in pairs from left to right, then the pairs are melded from
+    /// right to left. Returns the root of the tree.
+    fn pair(&mut self, first: Option<Key>) -> Option<Key> {
+        let mut pairs = Vec::new();
+
+        let mut current = first;
+        while let Some(a) = current {
+            let b = self.slot(a).sibling;
+            current = b.and_then(|b| self.slot(b).sibling);
+
+            self.unlink(a);
+            pairs.push(match b {
+                None => a,
+                Some(b) => {
+                    self.unlink(b);
+                    self.meld(a, b)
+                }
+            });
+        }
+
+        let mut tree = pairs.pop()?;
+        while let Some(t) = pairs.pop() {
+            tree = self.meld(t, tree);
+        }
+        Some(tree)
+    }
+
+    /// Clears the links of a node to its previous node and its sibling.
+    fn unlink(&mut self, x: Key) {
+        let slot = self.slot_mut(x);
+        slot.prev = None;
+        slot.sibling = None;
+    }
+}
+
+impl<T, C> PairingHeap<T, C> {
+    /// Returns the greatest element in the pairing heap,
+    /// or `None` if it is empty.
+    pub fn peek(&self) -> Option<&T> {
+        self.root.map(|x| &self.slot(x).item)
+    }
+
+    /// Returns an iterator that is visiting all values in the pairing heap,
+    /// in arbitrary order.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter::new(self.nodes.iter(), self.nodes.len())
+    }
+
+    /// Returns the value of the element with the given handle,
+    /// or `None` if the handle is not in the heap.
+    pub fn get(&self, handle: Handle) -> Option<&T> {
+        self.nodes.key(handle).map(|x| &self.slot(x).item)
+    }
+
+    /// Returns a flag indicating whenever the handle is in the heap.
+    pub fn contains(&self, handle: Handle) -> bool {
+        self.nodes.key(handle).is_some()
+    }
+
+    /// Returns the number of elements in the pairing heap.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns a flag indicating whenever the pairing heap is empty.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.len() == 0
+    }
+
+    /// Drops all the elements from the pairing heap.
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+        self.root = None;
+    }
+
+    /// Returns the number of elements the pairing heap can hold
+    /// without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.nodes.capacity()
+    }
+
+    /// Reserves capacity for at least `additional` more elements
+    /// to be inserted in the pairing heap.
+    pub fn reserve(&mut self, additional: usize) {
+        self.nodes.reserve(additional);
+    }
+
+    fn slot(&self, x: Key) -> &Slot<T> {
+        self.nodes.get(x).expect("the node is vacant")
+    }
+
+    fn slot_mut(&mut self, x: Key) -> &mut Slot<T> {
+        self.nodes.get_mut(x).expect("the node is vacant")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::lcg;
+
+    #[test]
+    fn new_pass() {
+        let h = PairingHeap::<u8>::new();
+        assert!(h.is_empty());
+        assert_eq!(None, h.peek());
+        assert_eq!(Ok(()), h.validate());
+    }
+
+    #[test]
+    fn default_pass() {
+        let mut h = PairingHeap::<u8, MinComparator>::default();
+        h.push(2);
+        h.push(1);
+        assert_eq!(Some(1), h.pop());
+    }
+
+    #[test]
+    fn debug_pass() {
+        let mut h = PairingHeap::new();
+        h.push(1);
+        assert_eq!("[1]", format!("{:?}", h));
+    }
+
+    #[test]
+    fn push_pop_pass() {
+        let mut seed = 3u64;
+        let xs: Vec<u64> = (0..1000).map(|_| lcg(&mut seed) % 300).collect();
+
+        let mut h = PairingHeap::new();
+        for x in xs.iter() {
+            h.push(*x);
+        }
+        assert_eq!(Ok(()), h.validate());
+
+        let mut ys = xs;
+        ys.sort_unstable_by(|a, b| b.cmp(a));
+        for y in ys {
+            assert_eq!(Some(y), h.pop());
+        }
+        assert_eq!(None, h.pop());
+        assert_eq!(Ok(()), h.validate());
+    }
+
+    #[test]
+    fn new_min_pass() {
+        let mut h = PairingHeap::new_min();
+        for x in [4, 2, 3, 5, 1].iter() {
+            h.push(*x);
+        }
+
+        for x in 1..6 {
+            assert_eq!(Some(x), h.pop());
+            assert_eq!(Ok(()), h.validate());
+        }
+    }
+
+    #[test]
+    fn with_comparator_pass() {
+        let mut h = PairingHeap::with_comparator(|a: &i32, b: &i32| b.abs().cmp(&a.abs()));
+        h.push(-3);
+        h.push(1);
+        h.push(-2);
+        assert_eq!(Some(1), h.pop());
+        assert_eq!(Some(-2), h.pop());
+    }
+
+    #[test]
+    fn keys_pass() {
+        let mut h = PairingHeap::new_min();
+        let hs: Vec<Handle> = (0..20).map(|x| h.push(x + 100)).collect();
+        h.pop();
+        assert_eq!(Ok(()), h.validate());
+
+        // Moving towards the top cuts the tree.
+        h.increase_key(hs[10], 5);
+        assert_eq!(Ok(()), h.validate());
+        assert_eq!(Some(&5), h.peek());
+
+        // Moving away from the top takes the node out.
+        h.decrease_key(hs[10], 200);
+        assert_eq!(Ok(()), h.validate());
+        assert_eq!(Some(&101), h.peek());
+        assert_eq!(Some(&200), h.get(hs[10]));
+
+        assert_eq!(Some(115), h.remove(hs[15]));
+        assert!(!h.contains(hs[15]));
+        assert_eq!(Ok(()), h.validate());
+        assert_eq!(18, h.len());
+    }
+
+    #[test]
+    fn keys_comparator_pass() {
+        // The direction of the update is given by the comparator, which
+        // disagrees here with the order of the tuples.
+        let mut h = PairingHeap::with_key(|x: &(u32, u32)| x.1);
+        h.push((0, 5));
+        let a = h.push((5, 1));
+        h.increase_key(a, (1, 10));
+        assert_eq!(Some(&(1, 10)), h.peek());
+        h.decrease_key(a, (9, 0));
+        assert_eq!(Some(&(0, 5)), h.peek());
+
+        // The elements don't have to implement `Ord`.
+        let mut h = PairingHeap::with_comparator(|a: &f64, b: &f64| a.total_cmp(b));
+        h.push(2.5);
+        let b = h.push(0.5);
+        h.increase_key(b, 4.0);
+        assert_eq!(Some(4.0), h.pop());
+        assert_eq!(Ok(()), h.validate());
+    }
+
+    #[test]
+    #[should_panic]
+    fn decrease_key_fail() {
+        let mut h = PairingHeap::new();
+        let x = h.push(1);
+        h.decrease_key(x, 10);
+    }
+
+    #[test]
+    fn append_pass() {
+        let mut a: PairingHeap<i32> = (0..10).collect();
+        let mut b: PairingHeap<i32> = (10..17).collect();
+        a.pop();
+        b.pop();
+
+        a.append(&mut b);
+        assert!(b.is_empty());
+        assert_eq!(15, a.len());
+        assert_eq!(Ok(()), a.validate());
+
+        let xs: Vec<i32> = std::iter::from_fn(|| a.pop()).collect();
+        let mut ys: Vec<i32> = (0..9).chain(10..16).collect();
+        ys.reverse();
+        assert_eq!(ys, xs);
+    }
+
+    #[test]
+    fn validate_fail() {
+        let mut h: PairingHeap<i32> = (0..4).collect();
+        let root = h.root.unwrap();
+        let child = h.slot(root).child.unwrap();
+        h.nodes.get_mut(child).unwrap().item = 100;
+
+        // The first child of the root follows it.
+        assert_eq!(
+            Err(InvariantError::Order {
+                parent: 0,
+                child: 1
+            }),
+            h.validate()
+        );
+    }
+
+    #[test]
+    fn clone_pass() {
+        let mut h: PairingHeap<i32> = (0..10).collect();
+        h.pop();
+
+        let mut c = h.clone();
+        assert_eq!(Ok(()), c.validate());
+        assert_eq!(h.pop(), c.pop());
+        assert_eq!(h.len(), c.len());
+    }
+
+    #[test]
+    fn clear_pass() {
+        let mut h: PairingHeap<i32> = (0..10).collect();
+        h.clear();
+        assert!(h.is_empty());
+        assert_eq!(None, h.pop());
+    }
+}
@@ -0,0 +1,8 @@
+//! Pairing heap
+mod convert;
+mod iter;
+mod main;
+
+pub use crate::arena::Handle;
+pub use crate::pairing_heap::iter::*;
+pub use crate::pairing_heap::main::PairingHeap;
@@ -0,0 +1,549 @@
+//! A storage made of arenas, which two heaps meld without moving the values.
+use crate::arena::{self, Arena, Handle};
+
+use alloc::collections::btree_map::{self, BTreeMap};
+use alloc::vec::Vec;
+use core::mem;
+
+/// The name of a value stored in a pool: the identity of its arena and
+/// its index in the arena.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub(crate) struct Key {
+    arena: usize,
+    index: usize,
+}
+
+/// A storage made of the arenas of one or more heaps. The values are
+/// inserted into the home arena of the pool, the other arenas were taken
+/// over from the heaps melded into this one.
+///
+/// The arenas are never copied: a meld moves the arenas of the other pool
+/// into this one, so the keys of the values and the handles given out by
+/// both pools stay valid. An arena taken over is dropped once it is empty.
+pub(crate) struct Pool<T> {
+    /// The arena of the inserted values.
+    home: Arena<T>,
+    /// The arenas taken over by the melds, by their identity.
+    others: BTreeMap<usize, Arena<T>>,
+    len: usize,
+}
+
+impl<T> Default for Pool<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Pool<T> {
+    /// Creates a new empty pool.
+    pub(crate) fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    /// Creates a new empty pool which can hold `capacity` values
+    /// without reallocating.
+    pub(crate) fn with_capacity(capacity: usize) -> Self {
+        Self {
+            home: Arena::with_capacity(capacity),
+            others: BTreeMap::new(),
+            len: 0,
+        }
+    }
+
+    /// Returns the number of values the pool can hold without reallocating:
+    /// the capacity of the home arena, and the values already stored in the
+    /// arenas taken over.
+    pub(crate) fn capacity(&self) -> usize {
+        self.home.capacity() + (self.len - self.home.len())
+    }
+
+    /// Reserves capacity for at least `additional` more values.
+    pub(crate) fn reserve(&mut self, additional: usize) {
+        self.home.reserve(additional);
+    }
+
+    /// Reserves the minimum capacity for exactly `additional` more values.
+    pub(crate) fn reserve_exact(&mut self, additional: usize) {
+        self.home.reserve_exact(additional);
+    }
+
+    /// Shrinks the allocated memory of all the arenas, the keys of the
+    /// stored values don't change.
+    pub(crate) fn shrink_to_fit(&mut self) {
+        self.home.shrink_to_fit();
+        self.others.values_mut().for_each(Arena::shrink_to_fit);
+    }
+
+    /// Returns the number of stored values.
+    pub(crate) fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns the key which the next insert returns.
+    pub(crate) fn vacant_key(&self) -> Key {
+        Key {
+            arena: self.home.id(),
+            index: self.home.vacant_index(),
+        }
+    }
+
+    /// Stores a value in the home arena and returns its key.
+    pub(crate) fn insert(&mut self, value: T) -> Key {
+        self.len += 1;
+        Key {
+            arena: self.home.id(),
+            index: self.home.insert(value),
+        }
+    }
+
+    /// Removes the value with the given key and returns it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no value with the key.
+    pub(crate) fn remove(&mut self, key: Key) -> T {
+        let value = if key.arena == self.home.id() {
+            self.home.remove(key.index)
+        } else {
+            let arena = self
+                .others
+                .get_mut(&key.arena)
+                .expect("the arena was dropped");
+            let value = arena.remove(key.index);
+            if arena.len() == 0 {
+                self.others.remove(&key.arena);
+            }
+            value
+        };
+
+        self.len -= 1;
+        value
+    }
+
+    /// Returns the value with the given key, or `None` if it was removed.
+    pub(crate) fn get(&self, key: Key) -> Option<&T> {
+        self.arena(key.arena)?.get(key.index)
+    }
+
+    /// Returns the value with the given key, or `None` if it was removed.
+    pub(crate) fn get_mut(&mut self, key: Key) -> Option<&mut T> {
+        if key.arena == self.home.id() {
+            self.home.get_mut(key.index)
+        } else {
+            self.others.get_mut(&key.arena)?.get_mut(key.index)
+        }
+    }
+
+    /// Returns the values with two different keys.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the keys are equal or if any of the values was removed.
+    pub(crate) fn get2_mut(&mut self, a: Key, b: Key) -> (&mut T, &mut T) {
+        if a.arena == b.arena {
+            let arena = if a.arena == self.home.id() {
+                &mut self.home
+            } else {
+                self.others
+                    .get_mut(&a.arena)
+                    .expect("the arena was dropped")
+            };
+            return arena.get2_mut(a.index, b.index);
+        }
+
+        let home = self.home.id();
+        let (x, y) = if a.arena == home {
+            let y = self
+                .others
+                .get_mut(&b.arena)
+                .expect("the arena was dropped");
+            (&mut self.home, y)
+        } else if b.arena == home {
+            let x = self
+                .others
+                .get_mut(&a.arena)
+                .expect("the arena was dropped");
+            (x, &mut self.home)
+        } else {
+            // Both arenas were taken over, borrow them from the two ends
+            // of a single range.
+            let (low, high) = (a.arena.min(b.arena), a.arena.max(b.arena));
+            let mut range = self.others.range_mut(low..=high);
+            let first = range.next().map(|(_, arena)| arena);
+            let last = range.next_back().map(|(_, arena)| arena);
+            match (first, last) {
+                (Some(first), Some(last)) if a.arena < b.arena => (first, last),
+                (Some(first), Some(last)) => (last, first),
+                _ => panic!("the arena was dropped"),
+            }
+        };
+
+        let x = x.get_mut(a.index).expect("the entry is vacant");
+        let y = y.get_mut(b.index).expect("the entry is vacant");
+        (x, y)
+    }
+
+    /// Returns the handle of the value with the given key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no value with the key.
+    pub(crate) fn handle(&self, key: Key) -> Handle {
+        self.arena(key.arena)
+            .expect("the arena was dropped")
+            .handle(key.index)
+    }
+
+    /// Returns the key of the value named by the handle, or `None` if the
+    /// value was removed or the handle was not given out by this pool or
+    /// by a pool melded into it.
+    pub(crate) fn key(&self, handle: Handle) -> Option<Key> {
+        let arena = self.arena(handle.arena())?;
+        arena.index(handle).map(|index| Key {
+            arena: arena.id(),
+            index,
+        })
+    }
+
+    /// Moves all the values of `other` into this pool, leaving it empty.
+    /// The arenas of `other` are taken over without moving the values, so
+    /// the keys and the handles of both pools stay valid. The time doesn't
+    /// depend on the number of values: each arena of the pool with fewer
+    /// arenas is added to the other one in `O(log k)` time, for `k` arenas.
+    pub(crate) fn append(&mut self, other: &mut Self) {
+        let mut other = mem::take(other);
+        if self.others.len() < other.others.len() {
+            mem::swap(&mut self.others, &mut other.others);
+        }
+
+        self.len += other.len;
+        let arenas = other.others.into_values();
+        for arena in arenas.chain(Some(other.home)) {
+            if arena.len() > 0 {
+                self.others.insert(arena.id(), arena);
+            }
+        }
+    }
+
+    /// Removes all the values. The home arena keeps counting its
+    /// generations and the other arenas are dropped, so the handles of
+    /// the removed values stay stale.
+    pub(crate) fn clear(&mut self) {
+        self.home.clear();
+        self.others.clear();
+        self.len = 0;
+    }
+
+    /// Returns an iterator over the keys and the stored values.
+    pub(crate) fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            arena: Some((self.home.id(), self.home.iter())),
+            others: self.others.values(),
+        }
+    }
+
+    /// Returns an iterator over the mutable stored values.
+    pub(crate) fn values_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        let home = Some(&mut self.home);
+        home.into_iter()
+            .chain(self.others.values_mut())
+            .flat_map(Arena::values_mut)
+    }
+
+    /// Returns the arena with the given identity.
+    fn arena(&self, id: usize) -> Option<&Arena<T>> {
+        if id == self.home.id() {
+            Some(&self.home)
+        } else {
+            self.others.get(&id)
+        }
+    }
+}
+
+impl<T: Clone> Pool<T> {
+    /// Clones the pool into arenas with new identities, so the handles of
+    /// this pool don't name the values of the clone. Returns the clone and
+    /// the renaming of the keys, which the caller applies to the keys
+    /// stored in the values.
+    pub(crate) fn clone_renamed(&self) -> (Self, Rename) {
+        let home = self.home.clone();
+        let mut arenas = Vec::with_capacity(self.others.len() + 1);
+        arenas.push((self.home.id(), home.id()));
+
+        let mut others = BTreeMap::new();
+        for (id, arena) in self.others.iter() {
+            let arena = arena.clone();
+            arenas.push((*id, arena.id()));
+            others.insert(arena.id(), arena);
+        }
+        arenas.sort_unstable();
+
+        let pool = Self {
+            home,
+            others,
+            len: self.len,
+        };
+        (pool, Rename { arenas })
+    }
+}
+
+/// The new keys of the values of a pool cloned by `Pool::clone_renamed`.
+pub(crate) struct Rename {
+    /// The old and the new identity of each arena, by the old identity.
+    arenas: Vec<(usize, usize)>,
+}
+
+impl Rename {
+    /// Returns the key of the cloned value.
+    pub(crate) fn key(&self, key: Key) -> Key {
+        let i = self
+            .arenas
+            .binary_search_by_key(&key.arena, |(old, _)| *old)
+            .expect("the key is not in the pool");
+        Key {
+            arena: self.arenas[i].1,
+            index: key.index,
+        }
+    }
+
+    /// Returns the key of the cloned value for an optional link.
+    pub(crate) fn link(&self, link: Option<Key>) -> Option<Key> {
+        link.map(|key| self.key(key))
+    }
+}
+
+impl<T> IntoIterator for Pool<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        let others: Vec<Arena<T>> = self.others.into_values().collect();
+        IntoIter {
+            arena: Some(self.home.into_iter()),
+            others: others.into_iter(),
+        }
+    }
+}
+
+/// An iterator over the keys and the values stored in a pool.
+pub(crate) struct Iter<'a, T> {
+    /// The identity of the current arena and the iterator over its values.
+    arena: Option<(usize, arena::Iter<'a, T>)>,
+    others: btree_map::Values<'a, usize, Arena<T>>,
+}
+
+impl<T> Clone for Iter<'_, T> {
+    fn clone(&self) -> Self {
+        Iter {
+            arena: self.arena.clone(),
+            others: self.others.clone(),
+        }
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = (Key, &'a T);
+
+    fn next(&mut self) -> Option<(Key, &'a T)> {
+        loop {
+            let (arena, iter) = self.arena.as_mut()?;
+            if let Some((index, value)) = iter.next() {
+                let key = Key {
+                    arena: *arena,
+                    index,
+                };
+                return Some((key, value));
+            }
+
+            self.arena = self.others.next().map(|arena| (arena.id(), arena.iter()));
+        }
+    }
+}
+
+/// An owning iterator over the values stored in a pool.
+pub(crate) struct IntoIter<T> {
+    arena: Option<arena::IntoIter<T>>,
+    others: alloc::vec::IntoIter<Arena<T>>,
+}
+
+impl<T> IntoIter<T> {
+    /// Returns an iterator over the values which were not consumed yet.
+    pub(crate) fn remaining(&self) -> impl Iterator<Item = &T> {
+        let others = self.others.as_slice().iter();
+        self.arena
+            .iter()
+            .flat_map(|iter| iter.remaining())
+            .chain(others.flat_map(|arena| arena.iter().map(|(_, value)| value)))
+    }
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        loop {
+            if let Some((_, value)) = self.arena.as_mut()?.next() {
+                return Some(value);
+            }
+
+            self.arena = self.others.next().map(Arena::into_iter);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insert_remove_pass() {
+        let mut p = Pool::new();
+        let x = p.insert(10);
+        let y = p.insert(20);
+        assert_eq!(2, p.len());
+        assert_eq!(Some(&10), p.get(x));
+
+        assert_eq!(10, p.remove(x));
+        assert_eq!(None, p.get(x));
+        assert_eq!(Some(&20), p.get(y));
+        assert_eq!(1, p.len());
+    }
+
+    #[test]
+    fn append_pass() {
+        let mut a = Pool::new();
+        let x = a.insert(1);
+        let hx = a.handle(x);
+
+        let mut b = Pool::new();
+        let ys: Vec<Key> = (2..5).map(|y| b.insert(y)).collect();
+        let hy = b.handle(ys[0]);
+
+        // Both pools keep the keys and the handles of their values.
+        a.append(&mut b);
+        assert_eq!(4, a.len());
+        assert_eq!(0, b.len());
+        assert_eq!(Some(x), a.key(hx));
+        assert_eq!(Some(ys[0]), a.key(hy));
+        assert_eq!(None, b.key(hy));
+        assert_eq!(Some(&3), a.get(ys[1]));
+
+        // The values are inserted into the home arena.
+        let z = a.insert(5);
+        assert_eq!(Some(&5), a.get(z));
+        assert_eq!(5, a.iter().count());
+
+        // The arena taken over is dropped once it is empty.
+        ys.iter().for_each(|y| {
+            a.remove(*y);
+        });
+        assert_eq!(None, a.key(hy));
+        assert_eq!(1, a.iter().skip(1).count());
+    }
+
+    #[test]
+    fn append_many_pass() {
+        let single = |x: usize| {
+            let mut p = Pool::new();
+            let k = p.insert(x);
+            let h = p.handle(k);
+            (p, h)
+        };
+
+        let mut a = Pool::new();
+        let mut b = Pool::new();
+        let mut handles = Vec::new();
+        for x in 0..5 {
+            let (mut p, h) = single(x);
+            handles.push(h);
+            if x < 2 { &mut a } else { &mut b }.append(&mut p);
+        }
+
+        // The pool with more arenas lends them to the other one.
+        a.append(&mut b);
+        for (x, h) in handles.iter().enumerate() {
+            assert_eq!(Some(&x), a.key(*h).and_then(|k| a.get(k)));
+        }
+
+        let mut xs: Vec<usize> = a.into_iter().collect();
+        xs.sort_unstable();
+        assert_eq!((0..5).collect::<Vec<_>>(), xs);
+    }
+
+    #[test]
+    fn get2_mut_pass() {
+        let mut a = Pool::new();
+        let x = a.insert(1);
+        let y = a.insert(2);
+
+        let mut b = Pool::new();
+        let z = b.insert(3);
+        let mut c = Pool::new();
+        let w = c.insert(4);
+        a.append(&mut b);
+        a.append(&mut c);
+
+        // The same arena, the home arena and another one, two other arenas.
+        for (k, l) in [(x, y), (y, z), (z, x), (w, z), (z, w)].iter() {
+            let (vk, vl) = a.get2_mut(*k, *l);
+            mem::swap(vk, vl);
+        }
+        assert_eq!(Some(&1), a.get(x));
+        assert_eq!(Some(&3), a.get(y));
+        assert_eq!(Some(&2), a.get(z));
+        assert_eq!(Some(&4), a.get(w));
+    }
+
+    #[test]
+    fn clone_renamed_pass() {
+        // Each value links to the key of the next value.
+        let mut a = Pool::new();
+        let x = a.insert(None);
+        let mut b = Pool::new();
+        let y = b.insert(Some(x));
+        a.append(&mut b);
+        *a.get_mut(x).unwrap() = Some(y);
+
+        let (mut c, rename) = a.clone_renamed();
+        c.values_mut().for_each(|link| *link = rename.link(*link));
+
+        let (cx, cy) = (rename.key(x), rename.key(y));
+        assert_eq!(Some(&Some(cy)), c.get(cx));
+        assert_eq!(Some(&Some(cx)), c.get(cy));
+
+        // The handles of the original don't name the values of the clone.
+        assert_eq!(None, c.key(a.handle(x)));
+        assert_eq!(Some(cx), c.key(c.handle(cx)));
+    }
+
+    #[test]
+    fn capacity_pass() {
+        let mut a = Pool::with_capacity(10);
+        assert!(a.capacity() >= 10);
+
+        let mut b = Pool::new();
+        (0..5).for_each(|x| {
+            b.insert(x);
+        });
+        a.append(&mut b);
+        assert!(a.capacity() >= 15);
+
+        a.clear();
+        assert_eq!(0, a.len());
+        assert_eq!(0, a.iter().count());
+    }
+
+    #[test]
+    fn into_iter_remaining_pass() {
+        let mut a = Pool::new();
+        a.insert(1);
+        let mut b = Pool::new();
+        b.insert(2);
+        a.append(&mut b);
+
+        let mut it = a.into_iter();
+        assert_eq!(2, it.remaining().count());
+        it.next();
+        assert_eq!(1, it.remaining().count());
+    }
+}
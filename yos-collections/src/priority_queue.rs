@@ -0,0 +1,374 @@
+//! A common interface for the heaps, so generic code can switch
+//! from one heap implementation to another.
+use crate::binary_heap::BinaryHeap;
+use crate::binomial_heap::BinomialHeap;
+use crate::compare::Compare;
+use crate::dary_heap::DaryHeap;
+use crate::fibonacci_heap::FibonacciHeap;
+use crate::min_max_heap::MinMaxHeap;
+use crate::pairing_heap::PairingHeap;
+use crate::stable_binary_heap::StableBinaryHeap;
+
+/// A priority queue, which hands out its elements starting with the greatest.
+///
+/// The trait is implemented by the binary, stable binary, d-ary, min-max,
+/// binomial, Fibonacci and pairing heaps. The min-max heap hands out its
+/// elements from the greatest, like the other max-heaps. Two heaps don't fit
+/// the trait and are left out: the `IndexedHeap`, where a push with a key
+/// which is already in the heap changes its priority instead of adding an
+/// element, and the `RadixHeap`, which pops its keys in increasing order and
+/// panics on a push below the last popped key.
+///
+/// # Example
+///
+/// ```
+/// use yos_collections::pairing_heap::PairingHeap;
+/// use yos_collections::priority_queue::PriorityQueue;
+///
+/// fn drain<Q: PriorityQueue<u32>>(mut queue: Q) -> Vec<u32> {
+///     core::iter::from_fn(|| queue.pop()).collect()
+/// }
+///
+/// let mut heap = PairingHeap::new();
+/// PriorityQueue::push(&mut heap, 1);
+/// PriorityQueue::push(&mut heap, 3);
+///
+/// assert_eq!(vec![3, 1], drain(heap));
+/// ```
+pub trait PriorityQueue<T> {
+    /// Pushes a value into the queue.
+    fn push(&mut self, item: T);
+
+    /// Removes the greatest value from the queue and returns it,
+    /// or `None` if the queue is empty.
+    fn pop(&mut self) -> Option<T>;
+
+    /// Returns the greatest value in the queue, or `None` if it is empty.
+    fn peek(&self) -> Option<&T>;
+
+    /// Returns the number of values in the queue.
+    fn len(&self) -> usize;
+
+    /// Returns a flag indicating whenever the queue is empty.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// A priority queue which can take over all the values of another queue.
+///
+/// # Example
+///
+/// ```
+/// use yos_collections::binomial_heap::BinomialHeap;
+/// use yos_collections::priority_queue::{Meldable, PriorityQueue};
+///
+/// let mut a: BinomialHeap<_> = vec![1, 5].into_iter().collect();
+/// let mut b: BinomialHeap<_> = vec![3, 7].into_iter().collect();
+///
+/// a.meld(&mut b);
+/// assert!(b.is_empty());
+/// assert_eq!(Some(7), PriorityQueue::pop(&mut a));
+/// ```
+pub trait Meldable<T>: PriorityQueue<T> {
+    /// Moves all the values of `other` into `self`, leaving `other` empty.
+    fn meld(&mut self, other: &mut Self);
+}
+
+impl<T, C: Compare<T>> PriorityQueue<T> for BinaryHeap<T, C> {
+    fn push(&mut self, item: T) {
+        BinaryHeap::push(self, item);
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        BinaryHeap::pop(self)
+    }
+
+    fn peek(&self) -> Option<&T> {
+        BinaryHeap::peek(self)
+    }
+
+    fn len(&self) -> usize {
+        BinaryHeap::len(self)
+    }
+}
+
+impl<T: Ord> PriorityQueue<T> for BinomialHeap<T> {
+    fn push(&mut self, item: T) {
+        BinomialHeap::push(self, item);
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        BinomialHeap::pop(self)
+    }
+
+    fn peek(&self) -> Option<&T> {
+        BinomialHeap::peek(self)
+    }
+
+    fn len(&self) -> usize {
+        BinomialHeap::len(self)
+    }
+}
+
+impl<T, C: Compare<T>> PriorityQueue<T> for PairingHeap<T, C> {
+    fn push(&mut self, item: T) {
+        PairingHeap::push(self, item);
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        PairingHeap::pop(self)
+    }
+
+    fn peek(&self) -> Option<&T> {
+        PairingHeap::peek(self)
+    }
+
+    fn len(&self) -> usize {
+        PairingHeap::len(self)
+    }
+}
+
+impl<T, const D: usize, C: Compare<T>> PriorityQueue<T> for DaryHeap<T, D, C> {
+    fn push(&mut self, item: T) {
+        DaryHeap::push(self, item);
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        DaryHeap::pop(self)
+    }
+
+    fn peek(&self) -> Option<&T> {
+        DaryHeap::peek(self)
+    }
+
+    fn len(&self) -> usize {
+        DaryHeap::len(self)
+    }
+}
+
+impl<T, C: Compare<T>> PriorityQueue<T> for FibonacciHeap<T, C> {
+    fn push(&mut self, item: T) {
+        FibonacciHeap::push(self, item);
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        FibonacciHeap::pop(self)
+    }
+
+    fn peek(&self) -> Option<&T> {
+        FibonacciHeap::peek(self)
+    }
+
+    fn len(&self) -> usize {
+        FibonacciHeap::len(self)
+    }
+}
+
+impl<T, C: Compare<T>> PriorityQueue<T> for StableBinaryHeap<T, C> {
+    fn push(&mut self, item: T) {
+        StableBinaryHeap::push(self, item);
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        StableBinaryHeap::pop(self)
+    }
+
+    fn peek(&self) -> Option<&T> {
+        StableBinaryHeap::peek(self)
+    }
+
+    fn len(&self) -> usize {
+        StableBinaryHeap::len(self)
+    }
+}
+
+impl<T: Ord> PriorityQueue<T> for MinMaxHeap<T> {
+    fn push(&mut self, item: T) {
+        MinMaxHeap::push(self, item);
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        MinMaxHeap::pop_max(self)
+    }
+
+    fn peek(&self) -> Option<&T> {
+        MinMaxHeap::peek_max(self)
+    }
+
+    fn len(&self) -> usize {
+        MinMaxHeap::len(self)
+    }
+}
+
+impl<T, C: Compare<T>> Meldable<T> for BinaryHeap<T, C> {
+    fn meld(&mut self, other: &mut Self) {
+        self.append(other);
+    }
+}
+
+impl<T, const D: usize, C: Compare<T>> Meldable<T> for DaryHeap<T, D, C> {
+    fn meld(&mut self, other: &mut Self) {
+        self.append(other);
+    }
+}
+
+impl<T: Ord> Meldable<T> for BinomialHeap<T> {
+    fn meld(&mut self, other: &mut Self) {
+        self.append(other);
+    }
+}
+
+impl<T, C: Compare<T>> Meldable<T> for FibonacciHeap<T, C> {
+    fn meld(&mut self, other: &mut Self) {
+        self.append(other);
+    }
+}
+
+impl<T, C: Compare<T>> Meldable<T> for PairingHeap<T, C> {
+    fn meld(&mut self, other: &mut Self) {
+        self.append(other);
+    }
+}
+
+impl<T, C: Compare<T>> Meldable<T> for StableBinaryHeap<T, C> {
+    fn meld(&mut self, other: &mut Self) {
+        self.append(other);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::lcg;
+
+    /// The conformance suite which every implementation of the traits has
+    /// to pass. The queue is created by the given expression. The queues
+    /// which implement only `PriorityQueue`, and not `Meldable`, are marked
+    /// as such and skip the meld.
+    macro_rules! conformance {
+        (@suite $name:ident, $new:expr, { $($meld:item)* }) => {
+            mod $name {
+                use super::*;
+
+                #[test]
+                fn empty_pass() {
+                    let mut q = $new;
+                    assert!(q.is_empty());
+                    assert_eq!(0, q.len());
+                    assert_eq!(None, q.peek());
+                    assert_eq!(None, q.pop());
+                }
+
+                #[test]
+                fn push_peek_pass() {
+                    let mut q = $new;
+                    q.push(2);
+                    assert_eq!(Some(&2), q.peek());
+                    q.push(5);
+                    assert_eq!(Some(&5), q.peek());
+                    q.push(1);
+                    assert_eq!(Some(&5), q.peek());
+                    assert_eq!(3, q.len());
+                    assert!(!q.is_empty());
+                }
+
+                #[test]
+                fn pop_order_pass() {
+                    let mut seed = 17u64;
+                    let xs: Vec<u64> = (0..500).map(|_| lcg(&mut seed) % 100).collect();
+
+                    let mut q = $new;
+                    for x in xs.iter() {
+                        q.push(*x);
+                    }
+
+                    let mut ys = xs;
+                    ys.sort_unstable_by(|a, b| b.cmp(a));
+                    let zs: Vec<u64> = std::iter::from_fn(|| q.pop()).collect();
+                    assert_eq!(ys, zs);
+                    assert!(q.is_empty());
+                }
+
+                #[test]
+                fn interleaved_pass() {
+                    let mut seed = 23u64;
+                    let mut q = $new;
+                    let mut model: Vec<u64> = Vec::new();
+
+                    for _ in 0..1000 {
+                        if lcg(&mut seed) % 3 == 0 {
+                            model.sort_unstable();
+                            assert_eq!(model.pop(), q.pop());
+                        } else {
+                            let x = lcg(&mut seed) % 50;
+                            model.push(x);
+                            q.push(x);
+                        }
+
+                        assert_eq!(model.len(), q.len());
+                        assert_eq!(model.iter().max(), q.peek());
+                    }
+                }
+
+                $($meld)*
+            }
+        };
+        ($name:ident, $new:expr, PriorityQueue) => {
+            conformance!(@suite $name, $new, {});
+        };
+        ($name:ident, $new:expr) => {
+            conformance!(@suite $name, $new, {
+                    #[test]
+                    fn meld_pass() {
+                        let mut a = $new;
+                        let mut b = $new;
+                        for x in 0..10 {
+                            a.push(x * 2);
+                            b.push(x * 2 + 1);
+                        }
+
+                        a.meld(&mut b);
+                        assert!(b.is_empty());
+                        assert_eq!(20, a.len());
+
+                        let xs: Vec<u64> = std::iter::from_fn(|| a.pop()).collect();
+                        assert_eq!((0..20).rev().collect::<Vec<u64>>(), xs);
+
+                        // Melding an empty queue changes nothing.
+                        b.push(3);
+                        a.meld(&mut b);
+                        assert_eq!(Some(3), a.pop());
+                    }
+            });
+        };
+    }
+
+    conformance!(binary_heap, BinaryHeap::<u64>::new());
+    conformance!(dary_heap_4, DaryHeap::<u64, 4>::new());
+    conformance!(dary_heap_8, DaryHeap::<u64, 8>::new());
+    conformance!(binomial_heap, BinomialHeap::<u64>::new());
+    conformance!(fibonacci_heap, FibonacciHeap::<u64>::new());
+    conformance!(pairing_heap, PairingHeap::<u64>::new());
+    conformance!(stable_binary_heap, StableBinaryHeap::<u64>::new());
+    conformance!(min_max_heap, MinMaxHeap::<u64>::new(), PriorityQueue);
+
+    fn sort<Q: PriorityQueue<i32>>(mut queue: Q, xs: &[i32]) -> Vec<i32> {
+        for x in xs {
+            queue.push(*x);
+        }
+        assert_eq!(xs.len(), queue.len());
+
+        std::iter::from_fn(|| queue.pop()).collect()
+    }
+
+    #[test]
+    fn switch_pass() {
+        let xs = [3, 1, 4, 1, 5, 9, 2, 6];
+        let expected = vec![9, 6, 5, 4, 3, 2, 1, 1];
+
+        assert_eq!(expected, sort(BinaryHeap::new(), &xs));
+        assert_eq!(expected, sort(BinomialHeap::new(), &xs));
+        assert_eq!(expected, sort(PairingHeap::new(), &xs));
+    }
+}
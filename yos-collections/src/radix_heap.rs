@@ -0,0 +1,363 @@
+//! Radix heap for monotone unsigned integer keys.
+use alloc::vec::Vec;
+use core::fmt::Debug;
+use core::mem::take;
+
+/// An unsigned integer which can be the key of a `RadixHeap`.
+pub trait RadixKey: Copy + Ord + Default + Debug {
+    /// The number of bits of the integer.
+    const BITS: u32;
+
+    /// Returns the position of the highest bit which differs between the
+    /// two integers, counted from 1, or 0 if they are equal.
+    fn radix_distance(&self, other: &Self) -> u32;
+}
+
+macro_rules! radix_key {
+    ($($t:ty),*) => {
+        $(
+            impl RadixKey for $t {
+                const BITS: u32 = <$t>::BITS;
+
+                #[inline]
+                fn radix_distance(&self, other: &Self) -> u32 {
+                    Self::BITS - (self ^ other).leading_zeros()
+                }
+            }
+        )*
+    };
+}
+
+radix_key!(u8, u16, u32, u64, u128, usize);
+
+/// Implementation of the radix heap, a min-heap for unsigned integer keys
+/// which are popped in non decreasing order, like the distances of
+/// Dijkstra's algorithm.
+///
+/// An element is kept in the bucket given by the highest bit where its key
+/// differs from the last popped key. When the bucket of the equal keys is
+/// empty, the first non empty bucket is split over the lower buckets around
+/// its smallest key. Every element moves to a lower bucket at most `BITS`
+/// times, so a pop is O(BITS) amortized and there are no comparisons
+/// between the keys along a path of the tree as in the binary heap.
+///
+/// The keys pushed into the heap may not be smaller than the last popped
+/// key, otherwise the push panics.
+pub struct RadixHeap<K, V> {
+    /// The bucket `i` keeps the keys whose highest bit which differs from
+    /// `last` is the bit `i`, the bucket 0 keeps the keys equal to `last`.
+    buckets: Vec<Vec<(K, V)>>,
+    /// The last popped key, a lower bound of all the keys in the heap.
+    last: K,
+    len: usize,
+}
+
+impl<K: Clone, V: Clone> Clone for RadixHeap<K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            buckets: self.buckets.clone(),
+            last: self.last.clone(),
+            len: self.len,
+        }
+    }
+}
+
+impl<K: RadixKey, V> Default for RadixHeap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Debug, V: Debug> Debug for RadixHeap<K, V> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list()
+            .entries(self.buckets.iter().flatten())
+            .finish()
+    }
+}
+
+impl<K: RadixKey, V> RadixHeap<K, V> {
+    /// Creates a new instance of the radix heap.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::radix_heap::RadixHeap;
+    ///
+    /// let mut heap = RadixHeap::new();
+    /// heap.push(3u32, "c");
+    /// heap.push(1, "a");
+    ///
+    /// assert_eq!(2, heap.len());
+    /// ```
+    pub fn new() -> Self {
+        Self {
+            buckets: (0..=K::BITS).map(|_| Vec::new()).collect(),
+            last: K::default(),
+            len: 0,
+        }
+    }
+
+    /// Pushes a value with the given key into the radix heap.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is smaller than the last popped key.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::radix_heap::RadixHeap;
+    ///
+    /// let mut heap = RadixHeap::new();
+    /// heap.push(5u64, 'x');
+    /// heap.push(2, 'y');
+    ///
+    /// assert_eq!(Some((2, 'y')), heap.pop());
+    /// heap.push(2, 'z');
+    /// assert_eq!(Some((2, 'z')), heap.pop());
+    /// ```
+    pub fn push(&mut self, key: K, value: V) {
+        assert!(
+            key >= self.last,
+            "the key {:?} is smaller than the last popped key {:?}",
+            key,
+            self.last
+        );
+
+        let bucket = key.radix_distance(&self.last) as usize;
+        self.buckets[bucket].push((key, value));
+        self.len += 1;
+    }
+
+    /// Removes the element with the smallest key from the radix heap and
+    /// returns it, or `None` if it is empty. Of several elements with the
+    /// smallest key, any one can be returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::radix_heap::RadixHeap;
+    ///
+    /// let mut heap = RadixHeap::new();
+    /// heap.push(7u8, ());
+    /// heap.push(3, ());
+    /// heap.push(9, ());
+    ///
+    /// assert_eq!(Some((3, ())), heap.pop());
+    /// assert_eq!(Some((7, ())), heap.pop());
+    /// ```
+    pub fn pop(&mut self) -> Option<(K, V)> {
+        if self.buckets[0].is_empty() {
+            let i = self.buckets.iter().position(|b| !b.is_empty())?;
+
+            // Split the bucket around its smallest key, every element goes
+            // to a lower bucket. The allocation of the bucket is kept.
+            let mut bucket = take(&mut self.buckets[i]);
+            self.last = bucket
+                .iter()
+                .map(|(k, _)| *k)
+                .min()
+                .expect("the bucket is not empty");
+
+            for (k, v) in bucket.drain(..) {
+                let j = k.radix_distance(&self.last) as usize;
+                self.buckets[j].push((k, v));
+            }
+            self.buckets[i] = bucket;
+        }
+
+        self.len -= 1;
+        self.buckets[0].pop()
+    }
+
+    /// Returns the element with the smallest key, the one the next pop
+    /// returns, or `None` if the radix heap is empty. When the bucket of
+    /// the keys equal to the last popped key is empty, the first non empty
+    /// bucket is scanned for its smallest key, without splitting it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::radix_heap::RadixHeap;
+    ///
+    /// let mut heap = RadixHeap::new();
+    /// assert_eq!(None, heap.peek());
+    ///
+    /// heap.push(7u8, 'a');
+    /// heap.push(3, 'b');
+    ///
+    /// assert_eq!(Some((&3, &'b')), heap.peek());
+    /// assert_eq!(Some((3, 'b')), heap.pop());
+    /// ```
+    pub fn peek(&self) -> Option<(&K, &V)> {
+        let bucket = self.buckets.iter().find(|b| !b.is_empty())?;
+
+        // The pop takes the last of the elements with the smallest key.
+        bucket
+            .iter()
+            .rev()
+            .min_by_key(|(k, _)| *k)
+            .map(|(k, v)| (k, v))
+    }
+
+    /// Returns the last popped key, which is a lower bound of the keys
+    /// which can be pushed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::radix_heap::RadixHeap;
+    ///
+    /// let mut heap = RadixHeap::new();
+    /// heap.push(4u16, ());
+    /// heap.pop();
+    ///
+    /// assert_eq!(4, heap.last_key());
+    /// ```
+    pub fn last_key(&self) -> K {
+        self.last
+    }
+
+    /// Removes all the elements from the radix heap, after which any key
+    /// can be pushed again.
+    pub fn clear(&mut self) {
+        self.buckets.iter_mut().for_each(Vec::clear);
+        self.last = K::default();
+        self.len = 0;
+    }
+}
+
+impl<K, V> RadixHeap<K, V> {
+    /// Returns the number of elements in the radix heap.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns a flag indicating whenever the radix heap is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl<K: RadixKey, V> Extend<(K, V)> for RadixHeap<K, V> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|(k, v)| self.push(k, v));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binary_heap::BinaryHeap;
+    use crate::test_util::lcg;
+
+    #[test]
+    fn distance_pass() {
+        assert_eq!(0, 5u8.radix_distance(&5));
+        assert_eq!(1, 4u8.radix_distance(&5));
+        assert_eq!(8, 255u8.radix_distance(&0));
+        assert_eq!(128, u128::MAX.radix_distance(&0));
+    }
+
+    #[test]
+    fn empty_pass() {
+        let mut h = RadixHeap::<u32, ()>::new();
+        assert!(h.is_empty());
+        assert_eq!(None, h.pop());
+        assert_eq!(0, h.len());
+    }
+
+    #[test]
+    fn monotone_pass() {
+        // A Dijkstra like workload: every push is at least the last pop.
+        let mut seed = 4u64;
+        let mut h = RadixHeap::<u64, ()>::new();
+        let mut model = BinaryHeap::new_min();
+
+        for _ in 0..5000 {
+            if lcg(&mut seed) % 3 == 1 {
+                let x = h.pop().map(|(k, _)| k);
+                assert_eq!(model.pop(), x);
+            } else {
+                let k = h.last_key() + lcg(&mut seed) % 1000;
+                h.push(k, ());
+                model.push(k);
+            }
+            assert_eq!(model.len(), h.len());
+        }
+
+        while let Some(k) = model.pop() {
+            assert_eq!(Some((k, ())), h.pop());
+        }
+        assert!(h.is_empty());
+    }
+
+    #[test]
+    fn peek_pass() {
+        // The peek names the element which the next pop returns.
+        let mut seed = 6u64;
+        let mut h = RadixHeap::<u32, u64>::new();
+
+        for step in 0..2000 {
+            match lcg(&mut seed) % 4 {
+                0 => {
+                    let x = h.peek().map(|(k, v)| (*k, *v));
+                    assert_eq!(x, h.pop());
+                }
+                _ => {
+                    let k = h.last_key() + (lcg(&mut seed) % 50) as u32;
+                    h.push(k, step);
+                }
+            }
+        }
+
+        while let Some(x) = h.peek().map(|(k, v)| (*k, *v)) {
+            assert_eq!(Some(x), h.pop());
+        }
+        assert_eq!(None, h.peek());
+    }
+
+    #[test]
+    fn extremes_pass() {
+        let mut h = RadixHeap::new();
+        h.extend(vec![(u64::MAX, 'c'), (0, 'a'), (1 << 63, 'b')]);
+
+        assert_eq!(Some((0, 'a')), h.pop());
+        assert_eq!(Some((1 << 63, 'b')), h.pop());
+        assert_eq!(Some((u64::MAX, 'c')), h.pop());
+        assert_eq!(None, h.pop());
+    }
+
+    #[test]
+    fn duplicates_pass() {
+        let mut h = RadixHeap::new();
+        h.extend(vec![(3u8, 1), (3, 2), (1, 3), (3, 4)]);
+
+        let mut xs: Vec<(u8, i32)> = std::iter::from_fn(|| h.pop()).collect();
+        assert_eq!((1, 3), xs[0]);
+        xs.sort_unstable();
+        assert_eq!(vec![(1, 3), (3, 1), (3, 2), (3, 4)], xs);
+    }
+
+    #[test]
+    fn clear_pass() {
+        let mut h = RadixHeap::new();
+        h.push(10usize, ());
+        h.pop();
+        h.clear();
+
+        h.push(1, ());
+        assert_eq!(Some((1, ())), h.pop());
+    }
+
+    #[test]
+    #[should_panic(expected = "smaller than the last popped key")]
+    fn monotone_fail() {
+        let mut h = RadixHeap::new();
+        h.push(10u32, ());
+        h.push(20, ());
+        h.pop();
+        h.push(5, ());
+    }
+}
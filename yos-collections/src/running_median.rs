@@ -0,0 +1,426 @@
+//! Streaming median, or any fixed percentile, from two binary heaps.
+use crate::binary_heap::BinaryHeap;
+use crate::compare::{Compare, MinComparator};
+
+use core::fmt::Debug;
+
+/// Tracks the median, or a fixed percentile, of a changing collection of
+/// values.
+///
+/// The values are split between a max-heap with the lower values and a
+/// min-heap with the upper values, so the tracked value is at the top of
+/// the lower heap. The lower heap keeps `floor(p * (n - 1)) + 1` values
+/// for the percentile `p` of `n` values: the lower median for `p = 0.5`.
+///
+/// A removed value is not searched for in the heaps. It is remembered in a
+/// third heap of the same side and dropped when it reaches the top, so the
+/// memory of a sliding window holds the values which left the window but
+/// did not reach the top yet.
+pub struct RunningMedian<T> {
+    low: BinaryHeap<T>,
+    high: BinaryHeap<T, MinComparator>,
+    low_removed: BinaryHeap<T>,
+    high_removed: BinaryHeap<T, MinComparator>,
+    /// The number of values in the lower heap which were not removed.
+    low_len: usize,
+    /// The number of values in the upper heap which were not removed.
+    high_len: usize,
+    percentile: f64,
+}
+
+impl<T: Clone> Clone for RunningMedian<T> {
+    fn clone(&self) -> Self {
+        Self {
+            low: self.low.clone(),
+            high: self.high.clone(),
+            low_removed: self.low_removed.clone(),
+            high_removed: self.high_removed.clone(),
+            low_len: self.low_len,
+            high_len: self.high_len,
+            percentile: self.percentile,
+        }
+    }
+}
+
+impl<T: Ord> Default for RunningMedian<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord + Debug> Debug for RunningMedian<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("RunningMedian")
+            .field("percentile", &self.percentile)
+            .field("len", &self.len())
+            .field("value", &self.get())
+            .finish()
+    }
+}
+
+impl<T: Ord> RunningMedian<T> {
+    /// Creates a new instance which tracks the median.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::running_median::RunningMedian;
+    ///
+    /// let mut m = RunningMedian::new();
+    /// m.push(5);
+    /// m.push(1);
+    /// m.push(3);
+    ///
+    /// assert_eq!(Some(&3), m.median());
+    /// ```
+    pub fn new() -> Self {
+        Self::with_percentile(0.5)
+    }
+
+    /// Creates a new instance which tracks the given percentile, a number
+    /// between `0.0` (the smallest value) and `1.0` (the greatest value).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the percentile is not between `0.0` and `1.0`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::running_median::RunningMedian;
+    ///
+    /// let mut p90 = RunningMedian::with_percentile(0.9);
+    /// p90.extend(1..=11);
+    ///
+    /// assert_eq!(Some(&10), p90.get());
+    /// ```
+    pub fn with_percentile(percentile: f64) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&percentile),
+            "the percentile {} is not between 0 and 1",
+            percentile
+        );
+
+        Self {
+            low: BinaryHeap::new(),
+            high: BinaryHeap::new_min(),
+            low_removed: BinaryHeap::new(),
+            high_removed: BinaryHeap::new_min(),
+            low_len: 0,
+            high_len: 0,
+            percentile,
+        }
+    }
+
+    /// Adds a value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::running_median::RunningMedian;
+    ///
+    /// let mut m = RunningMedian::new();
+    /// m.push(2);
+    /// m.push(8);
+    ///
+    /// assert_eq!(Some(&2), m.median());
+    /// assert_eq!(2, m.len());
+    /// ```
+    pub fn push(&mut self, value: T) {
+        match self.low.peek() {
+            Some(top) if value <= *top => {
+                self.low.push(value);
+                self.low_len += 1;
+            }
+            _ => {
+                self.high.push(value);
+                self.high_len += 1;
+            }
+        }
+
+        self.balance();
+    }
+
+    /// Removes a value, which was added before and not removed yet. This is
+    /// how the median of a sliding window is kept: every value which leaves
+    /// the window is removed.
+    ///
+    /// Removing a value which is not tracked breaks the later results.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no tracked value on the side of the value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::running_median::RunningMedian;
+    ///
+    /// let xs = [5, 1, 9, 3, 7];
+    /// let mut m = RunningMedian::new();
+    ///
+    /// let mut medians = Vec::new();
+    /// for (i, x) in xs.iter().enumerate() {
+    ///     m.push(*x);
+    ///     if i >= 3 {
+    ///         m.remove(xs[i - 3]);
+    ///     }
+    ///     medians.push(*m.median().unwrap());
+    /// }
+    ///
+    /// assert_eq!(vec![5, 1, 5, 3, 7], medians);
+    /// ```
+    pub fn remove(&mut self, value: T) {
+        // The lower heap keeps all the values up to its top.
+        match self.low.peek() {
+            Some(top) if value <= *top => {
+                self.low_len -= 1;
+                self.low_removed.push(value);
+                Self::prune(&mut self.low, &mut self.low_removed);
+            }
+            _ => {
+                assert!(self.high_len > 0, "the removed value is not tracked");
+                self.high_len -= 1;
+                self.high_removed.push(value);
+                Self::prune(&mut self.high, &mut self.high_removed);
+            }
+        }
+
+        self.balance();
+    }
+
+    /// Returns the median, the lower one of the two middle values for an
+    /// even number of values, or `None` if there are no values. For an
+    /// instance created with `with_percentile` it is the same as `get`.
+    pub fn median(&self) -> Option<&T> {
+        self.get()
+    }
+
+    /// Returns the value at the tracked percentile, the value of rank
+    /// `floor(p * (n - 1))` in ascending order, or `None` if there are
+    /// no values.
+    pub fn get(&self) -> Option<&T> {
+        self.low.peek()
+    }
+
+    /// Returns the values of ranks `floor(p * (n - 1))` and
+    /// `ceil(p * (n - 1))`, which are the same value when the rank is a
+    /// whole number. For the median of an even number of values these are
+    /// the two middle values, which can be averaged.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::running_median::RunningMedian;
+    ///
+    /// let mut m = RunningMedian::new();
+    /// m.extend(vec![4, 1, 3, 2]);
+    ///
+    /// assert_eq!(Some((&2, &3)), m.median_pair());
+    /// m.push(5);
+    /// assert_eq!(Some((&3, &3)), m.median_pair());
+    /// ```
+    pub fn median_pair(&self) -> Option<(&T, &T)> {
+        let lower = self.low.peek()?;
+        let rank = self.percentile * (self.len() - 1) as f64;
+
+        // The rank is not negative, the cast rounds it down.
+        if rank > (rank as usize) as f64 {
+            self.high.peek().map(|upper| (lower, upper))
+        } else {
+            Some((lower, lower))
+        }
+    }
+
+    /// Removes all the values.
+    pub fn clear(&mut self) {
+        self.low.clear();
+        self.high.clear();
+        self.low_removed.clear();
+        self.high_removed.clear();
+        self.low_len = 0;
+        self.high_len = 0;
+    }
+
+    /// Returns the tracked percentile.
+    pub fn percentile(&self) -> f64 {
+        self.percentile
+    }
+
+    /// Returns the number of tracked values.
+    pub fn len(&self) -> usize {
+        self.low_len + self.high_len
+    }
+
+    /// Returns a flag indicating whenever there are no tracked values.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of values the lower heap has to keep.
+    fn low_target(&self) -> usize {
+        match self.len() {
+            0 => 0,
+            // The rank is not negative, the cast rounds it down.
+            n => (self.percentile * (n - 1) as f64) as usize + 1,
+        }
+    }
+
+    /// Moves the tops between the heaps until the lower heap keeps
+    /// the target number of values.
+    fn balance(&mut self) {
+        let target = self.low_target();
+
+        while self.low_len > target {
+            let value = self.low.pop().expect("the lower heap is not empty");
+            Self::prune(&mut self.low, &mut self.low_removed);
+            self.low_len -= 1;
+
+            self.high.push(value);
+            self.high_len += 1;
+        }
+
+        while self.low_len < target {
+            let value = self.high.pop().expect("the upper heap is not empty");
+            Self::prune(&mut self.high, &mut self.high_removed);
+            self.high_len -= 1;
+
+            self.low.push(value);
+            self.low_len += 1;
+        }
+    }
+
+    /// Drops the removed values from the top of a heap.
+    fn prune<C>(heap: &mut BinaryHeap<T, C>, removed: &mut BinaryHeap<T, C>)
+    where
+        C: Compare<T>,
+    {
+        while let (Some(top), Some(gone)) = (heap.peek(), removed.peek()) {
+            if top != gone {
+                break;
+            }
+
+            heap.pop();
+            removed.pop();
+        }
+    }
+}
+
+impl<T: Ord> Extend<T> for RunningMedian<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|x| self.push(x));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::lcg;
+
+    fn rank(xs: &[u64], p: f64) -> u64 {
+        let mut ys = xs.to_vec();
+        ys.sort_unstable();
+        ys[(p * (ys.len() - 1) as f64).floor() as usize]
+    }
+
+    #[test]
+    fn empty_pass() {
+        let m = RunningMedian::<u8>::new();
+        assert!(m.is_empty());
+        assert_eq!(None, m.median());
+        assert_eq!(None, m.median_pair());
+    }
+
+    #[test]
+    fn push_pass() {
+        let mut seed = 1u64;
+        let mut m = RunningMedian::new();
+        let mut xs = Vec::new();
+
+        for _ in 0..500 {
+            let x = lcg(&mut seed) % 100;
+            xs.push(x);
+            m.push(x);
+            assert_eq!(Some(&rank(&xs, 0.5)), m.median());
+        }
+    }
+
+    #[test]
+    fn sliding_window_pass() {
+        let mut seed = 2u64;
+        let xs: Vec<u64> = (0..2000).map(|_| lcg(&mut seed) % 50).collect();
+        let window = 25;
+
+        let mut m = RunningMedian::new();
+        for (i, x) in xs.iter().enumerate() {
+            m.push(*x);
+            if i >= window {
+                m.remove(xs[i - window]);
+            }
+
+            let start = (i + 1).saturating_sub(window);
+            assert_eq!(i + 1 - start, m.len());
+            assert_eq!(Some(&rank(&xs[start..=i], 0.5)), m.median());
+        }
+    }
+
+    #[test]
+    fn percentile_pass() {
+        for p in [0.0, 0.1, 0.9, 0.99, 1.0].iter() {
+            let mut seed = 3u64;
+            let xs: Vec<u64> = (0..1000).map(|_| lcg(&mut seed) % 1000).collect();
+            let window = 100;
+
+            let mut m = RunningMedian::with_percentile(*p);
+            for (i, x) in xs.iter().enumerate() {
+                m.push(*x);
+                if i >= window {
+                    m.remove(xs[i - window]);
+                }
+
+                let start = (i + 1).saturating_sub(window);
+                assert_eq!(Some(&rank(&xs[start..=i], *p)), m.get());
+            }
+        }
+    }
+
+    #[test]
+    fn remove_all_pass() {
+        let mut m = RunningMedian::new();
+        m.extend(vec![3, 3, 1, 2, 3]);
+
+        for x in [3, 1, 3, 2, 3].iter() {
+            m.remove(*x);
+        }
+        assert!(m.is_empty());
+        assert_eq!(None, m.median());
+
+        m.push(7);
+        assert_eq!(Some(&7), m.median());
+    }
+
+    #[test]
+    fn median_pair_pass() {
+        let mut m = RunningMedian::new();
+        m.push(10);
+        assert_eq!(Some((&10, &10)), m.median_pair());
+        m.push(20);
+        assert_eq!(Some((&10, &20)), m.median_pair());
+        m.remove(10);
+        assert_eq!(Some((&20, &20)), m.median_pair());
+    }
+
+    #[test]
+    #[should_panic(expected = "between 0 and 1")]
+    fn percentile_fail() {
+        RunningMedian::<u8>::with_percentile(1.5);
+    }
+
+    #[test]
+    #[should_panic(expected = "not tracked")]
+    fn remove_fail() {
+        let mut m = RunningMedian::new();
+        m.push(1);
+        m.remove(5);
+    }
+}
@@ -0,0 +1,87 @@
+use super::main::{Entry, StableBinaryHeap};
+use crate::compare::{Compare, MaxComparator};
+use alloc::vec::Vec;
+use core::iter::FromIterator;
+
+impl<T: Ord> From<Vec<T>> for StableBinaryHeap<T> {
+    /// Converts a `Vec<T>` into a `StableBinaryHeap<T>`. The elements count
+    /// as pushed in the order of the vector.
+    ///
+    /// This conversion has `O(n)` time complexity.
+    fn from(vec: Vec<T>) -> Self {
+        let mut heap = Self::from_comparator(MaxComparator);
+        heap.extend(vec);
+        heap
+    }
+}
+
+impl<T, C> From<StableBinaryHeap<T, C>> for Vec<T> {
+    fn from(heap: StableBinaryHeap<T, C>) -> Vec<T> {
+        heap.into_iter().collect()
+    }
+}
+
+impl<T: Ord> FromIterator<T> for StableBinaryHeap<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> StableBinaryHeap<T> {
+        StableBinaryHeap::from(iter.into_iter().collect::<Vec<_>>())
+    }
+}
+
+impl<T, C: Compare<T>> Extend<T> for StableBinaryHeap<T, C> {
+    /// Extends the stable binary heap with the elements of an iterator,
+    /// which count as pushed in the order of the iterator. When many
+    /// elements arrive at once the heap is rebuilt instead of pushing them
+    /// one by one.
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let seq = self.seq;
+        let start = self.heap.data.len();
+        self.heap.data.extend(
+            iter.into_iter()
+                .zip(seq..)
+                .map(|(item, seq)| Entry { seq, item }),
+        );
+
+        self.seq += (self.heap.data.len() - start) as u64;
+        self.heap.rebuild_tail(start);
+    }
+}
+
+impl<'a, T: 'a + Copy, C: Compare<T>> Extend<&'a T> for StableBinaryHeap<T, C> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().cloned());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_vec_to_vec() {
+        let mut h = StableBinaryHeap::from(vec![3, 1, 4]);
+        h.push(2);
+
+        let xs: Vec<i32> = h.into();
+        assert_eq!(xs.len(), 4);
+    }
+
+    #[test]
+    fn from_iterator() {
+        let h: StableBinaryHeap<_> = (0..100).collect();
+        assert_eq!(Ok(()), h.validate());
+        assert_eq!(Some(&99), h.peek());
+    }
+
+    #[test]
+    fn extend_pass() {
+        let mut h = StableBinaryHeap::from((0..100).collect::<Vec<_>>());
+        h.extend(&[200, -5]);
+        assert_eq!(Ok(()), h.validate());
+        assert_eq!(Some(&200), h.peek());
+
+        h.extend(1000..2000);
+        assert_eq!(Ok(()), h.validate());
+        assert_eq!(Some(&1999), h.peek());
+        assert_eq!(1102, h.seq);
+    }
+}
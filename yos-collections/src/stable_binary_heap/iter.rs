@@ -0,0 +1,234 @@
+//! Iterators over the elements in a stable binary heap.
+use super::main::{Entry, StableBinaryHeap};
+use crate::binary_heap;
+use crate::compare::Compare;
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::Debug;
+use core::iter::FusedIterator;
+use core::slice;
+
+/// An iterator over the elements of a `StableBinaryHeap`.
+pub struct Iter<'a, T: 'a> {
+    iter: slice::Iter<'a, Entry<T>>,
+}
+
+impl<'a, T> Iter<'a, T> {
+    /// Creates a new instance of the iterator.
+    pub(crate) fn new(iter: slice::Iter<'a, Entry<T>>) -> Self {
+        Self { iter }
+    }
+}
+
+impl<T: Debug> Debug for Iter<'_, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("Iter")
+            .field(&self.clone().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl<T> Clone for Iter<'_, T> {
+    fn clone(&self) -> Self {
+        Iter {
+            iter: self.iter.clone(),
+        }
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a T> {
+        self.iter.next().map(|entry| &entry.item)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<&'a T> {
+        self.iter.next_back().map(|entry| &entry.item)
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+impl<T> FusedIterator for Iter<'_, T> {}
+
+/// An owning iterator over the elements of a `StableBinaryHeap`.
+#[derive(Clone)]
+pub struct IntoIter<T> {
+    iter: vec::IntoIter<Entry<T>>,
+}
+
+impl<T> IntoIter<T> {
+    /// Creates a new instance of the into iterator.
+    pub(crate) fn new(iter: vec::IntoIter<Entry<T>>) -> Self {
+        Self { iter }
+    }
+}
+
+impl<T: Debug> Debug for IntoIter<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let items: Vec<&T> = self.iter.as_slice().iter().map(|e| &e.item).collect();
+        f.debug_tuple("IntoIter").field(&items).finish()
+    }
+}
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        self.iter.next().map(|entry| entry.item)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoIter<T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<T> {
+        self.iter.next_back().map(|entry| entry.item)
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> FusedIterator for IntoIter<T> {}
+
+/// A draining iterator over the elements of a `StableBinaryHeap`.
+pub struct Drain<'a, T: 'a> {
+    iter: binary_heap::Drain<'a, Entry<T>>,
+}
+
+impl<'a, T> Drain<'a, T> {
+    /// Creates a new instance of the draining iterator.
+    pub(crate) fn new(iter: binary_heap::Drain<'a, Entry<T>>) -> Self {
+        Self { iter }
+    }
+}
+
+impl<T: Debug> Debug for Drain<'_, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("Drain").finish()
+    }
+}
+
+impl<T> Iterator for Drain<'_, T> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        self.iter.next().map(|entry| entry.item)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for Drain<'_, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<T> {
+        self.iter.next_back().map(|entry| entry.item)
+    }
+}
+
+impl<T> ExactSizeIterator for Drain<'_, T> {}
+
+impl<T> FusedIterator for Drain<'_, T> {}
+
+/// An owning iterator over the elements of a `StableBinaryHeap`,
+/// which yields the elements in heap order.
+#[derive(Clone)]
+pub struct IntoIterSorted<T, C> {
+    heap: StableBinaryHeap<T, C>,
+}
+
+impl<T, C> IntoIterSorted<T, C> {
+    /// Creates a new instance of the sorted into iterator.
+    pub fn new(heap: StableBinaryHeap<T, C>) -> Self {
+        Self { heap }
+    }
+}
+
+impl<T: Debug, C> Debug for IntoIterSorted<T, C> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("IntoIterSorted").field(&self.heap).finish()
+    }
+}
+
+impl<T, C: Compare<T>> Iterator for IntoIterSorted<T, C> {
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        self.heap.pop()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.heap.len();
+        (len, Some(len))
+    }
+}
+
+impl<T, C: Compare<T>> ExactSizeIterator for IntoIterSorted<T, C> {}
+
+impl<T, C: Compare<T>> FusedIterator for IntoIterSorted<T, C> {}
+
+impl<T, C> IntoIterator for StableBinaryHeap<T, C> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter::new(self.heap.data.into_iter())
+    }
+}
+
+impl<'a, T, C> IntoIterator for &'a StableBinaryHeap<T, C> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn into_iter_pass() {
+        let h = StableBinaryHeap::from(vec![1, 2, 3]);
+
+        let mut xs: Vec<&i32> = (&h).into_iter().collect();
+        xs.sort_unstable();
+        assert_eq!(vec![&1, &2, &3], xs);
+
+        let mut xs: Vec<i32> = h.into_iter().collect();
+        xs.sort_unstable();
+        assert_eq!(vec![1, 2, 3], xs);
+    }
+
+    #[test]
+    fn debug_pass() {
+        let h = StableBinaryHeap::from(vec![2]);
+        assert_eq!("Iter([2])", format!("{:?}", h.iter()));
+        assert_eq!("IntoIter([2])", format!("{:?}", h.clone().into_iter()));
+        assert_eq!("IntoIterSorted([2])", format!("{:?}", h.into_iter_sorted()));
+    }
+}
@@ -0,0 +1,581 @@
+//! Implementation of the stable binary heap
+use super::iter::*;
+use super::peek_mut::PeekMut;
+use crate::binary_heap::BinaryHeap;
+use crate::compare::{Compare, FnComparator, KeyComparator, MaxComparator, MinComparator};
+use crate::invariant::InvariantError;
+
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt::Debug;
+
+/// An element of the heap together with its insertion sequence.
+#[derive(Clone, Debug)]
+pub(crate) struct Entry<T> {
+    pub(crate) seq: u64,
+    pub(crate) item: T,
+}
+
+/// Orders the entries by the comparator and breaks the ties by the
+/// insertion sequence, the earlier entry is kept closer to the top.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Stable<C>(pub(crate) C);
+
+impl<T, C: Compare<T>> Compare<Entry<T>> for Stable<C> {
+    #[inline]
+    fn compare(&self, a: &Entry<T>, b: &Entry<T>) -> Ordering {
+        self.0
+            .compare(&a.item, &b.item)
+            .then_with(|| b.seq.cmp(&a.seq))
+    }
+}
+
+/// Implementation of a binary heap which hands out the equal elements in
+/// the order they were pushed, first in first out.
+///
+/// Every element is stored with the sequence number of its insertion, and
+/// the ties of the comparator `C` are broken by that sequence. By default
+/// the heap is a max-heap, which keeps the greatest element at the top.
+pub struct StableBinaryHeap<T, C = MaxComparator> {
+    pub(crate) heap: BinaryHeap<Entry<T>, Stable<C>>,
+    /// The sequence number of the next pushed element.
+    pub(crate) seq: u64,
+}
+
+impl<T: Clone, C: Clone> Clone for StableBinaryHeap<T, C> {
+    fn clone(&self) -> Self {
+        Self {
+            heap: self.heap.clone(),
+            seq: self.seq,
+        }
+    }
+
+    fn clone_from(&mut self, source: &Self) {
+        self.heap.clone_from(&source.heap);
+        self.seq = source.seq;
+    }
+}
+
+impl<T, C: Compare<T> + Default> Default for StableBinaryHeap<T, C> {
+    fn default() -> Self {
+        Self::from_comparator(C::default())
+    }
+}
+
+impl<T: Debug, C> Debug for StableBinaryHeap<T, C> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: Ord> StableBinaryHeap<T> {
+    /// Creates a new instance of the stable binary heap.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::stable_binary_heap::StableBinaryHeap;
+    ///
+    /// let mut heap = StableBinaryHeap::new();
+    /// heap.push(1);
+    /// heap.push(3);
+    /// heap.push(2);
+    ///
+    /// assert_eq!(3, heap.len());
+    /// ```
+    pub fn new() -> Self {
+        Self::from_comparator(MaxComparator)
+    }
+
+    /// Creates a new instance of the stable binary heap with a given capacity.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::stable_binary_heap::StableBinaryHeap;
+    ///
+    /// let heap = StableBinaryHeap::<u8>::with_capacity(10);
+    /// assert!(heap.capacity() >= 10);
+    /// ```
+    pub fn with_capacity(capacity: usize) -> Self {
+        let mut heap = Self::new();
+        heap.reserve_exact(capacity);
+        heap
+    }
+}
+
+impl<T: Ord> StableBinaryHeap<T, MinComparator> {
+    /// Creates a new instance of the stable binary heap which keeps the
+    /// smallest element at the top.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::stable_binary_heap::StableBinaryHeap;
+    ///
+    /// let mut heap = StableBinaryHeap::new_min();
+    /// heap.push(3);
+    /// heap.push(1);
+    /// heap.push(2);
+    ///
+    /// assert_eq!(Some(1), heap.pop());
+    /// ```
+    pub fn new_min() -> Self {
+        Self::from_comparator(MinComparator)
+    }
+}
+
+impl<T, F> StableBinaryHeap<T, FnComparator<F>>
+where
+    F: Fn(&T, &T) -> Ordering,
+{
+    /// Creates a new instance of the stable binary heap which orders the
+    /// elements with a closure. The greatest element, according to the
+    /// closure, is kept at the top.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::stable_binary_heap::StableBinaryHeap;
+    ///
+    /// let mut heap = StableBinaryHeap::with_comparator(|a: &i32, b: &i32| a.abs().cmp(&b.abs()));
+    /// heap.push(-3);
+    /// heap.push(1);
+    /// heap.push(3);
+    ///
+    /// assert_eq!(Some(-3), heap.pop());
+    /// assert_eq!(Some(3), heap.pop());
+    /// ```
+    pub fn with_comparator(f: F) -> Self {
+        Self::from_comparator(FnComparator(f))
+    }
+}
+
+impl<T, K, F> StableBinaryHeap<T, KeyComparator<F>>
+where
+    K: Ord,
+    F: Fn(&T) -> K,
+{
+    /// Creates a new instance of the stable binary heap which orders the
+    /// elements by a key extracted with a closure. The elements with the
+    /// same key are handed out in the order they were pushed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::stable_binary_heap::StableBinaryHeap;
+    ///
+    /// let mut heap = StableBinaryHeap::with_key(|t: &(&str, u32)| t.1);
+    /// heap.push(("first", 1));
+    /// heap.push(("second", 1));
+    /// heap.push(("urgent", 9));
+    ///
+    /// assert_eq!(Some(("urgent", 9)), heap.pop());
+    /// assert_eq!(Some(("first", 1)), heap.pop());
+    /// assert_eq!(Some(("second", 1)), heap.pop());
+    /// ```
+    pub fn with_key(f: F) -> Self {
+        Self::from_comparator(KeyComparator(f))
+    }
+}
+
+impl<T, C: Compare<T>> StableBinaryHeap<T, C> {
+    /// Creates a new instance of the stable binary heap which orders
+    /// the elements with the given comparator.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::compare::MinComparator;
+    /// use yos_collections::stable_binary_heap::StableBinaryHeap;
+    ///
+    /// let mut heap = StableBinaryHeap::from_comparator(MinComparator);
+    /// heap.push(2);
+    /// heap.push(1);
+    ///
+    /// assert_eq!(Some(1), heap.pop());
+    /// ```
+    pub fn from_comparator(cmp: C) -> Self {
+        Self {
+            heap: BinaryHeap::from_comparator(Stable(cmp)),
+            seq: 0,
+        }
+    }
+
+    /// Removes the greatest item from the stable binary heap and returns it,
+    /// or `None` if it is empty. Of several greatest items, the one which
+    /// was pushed first is removed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::stable_binary_heap::StableBinaryHeap;
+    ///
+    /// let mut heap = StableBinaryHeap::with_key(|x: &(u8, char)| x.0);
+    /// heap.push((1, 'a'));
+    /// heap.push((1, 'b'));
+    ///
+    /// assert_eq!(Some((1, 'a')), heap.pop());
+    /// assert_eq!(Some((1, 'b')), heap.pop());
+    /// assert_eq!(None, heap.pop());
+    /// ```
+    pub fn pop(&mut self) -> Option<T> {
+        self.heap.pop().map(|entry| entry.item)
+    }
+
+    /// Pushes a value into the stable binary heap.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::stable_binary_heap::StableBinaryHeap;
+    ///
+    /// let mut heap = StableBinaryHeap::new();
+    /// heap.push(1);
+    /// heap.push(3);
+    ///
+    /// assert_eq!(Some(&3), heap.peek());
+    /// ```
+    pub fn push(&mut self, value: T) {
+        let seq = self.next_seq();
+        self.heap.push(Entry { seq, item: value });
+    }
+
+    /// Returns a guard which gives mutable access to the greatest element,
+    /// or `None` if the stable binary heap is empty. The element keeps its
+    /// insertion sequence, and the heap is fixed when the guard is dropped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::stable_binary_heap::StableBinaryHeap;
+    ///
+    /// let mut heap = StableBinaryHeap::from(vec![1, 5, 3]);
+    /// if let Some(mut top) = heap.peek_mut() {
+    ///     *top = 2;
+    /// }
+    ///
+    /// assert_eq!(Some(&3), heap.peek());
+    /// ```
+    pub fn peek_mut(&mut self) -> Option<PeekMut<'_, T, C>> {
+        self.heap.peek_mut().map(PeekMut::new)
+    }
+
+    /// Moves all the elements of `other` into `self`, leaving `other` empty.
+    /// The elements of `other` count as pushed after the elements of `self`,
+    /// in the order they were pushed into `other`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::stable_binary_heap::StableBinaryHeap;
+    ///
+    /// let key = |x: &(u8, char)| x.0;
+    /// let mut a = StableBinaryHeap::with_key(key);
+    /// let mut b = StableBinaryHeap::with_key(key);
+    /// b.push((1, 'b'));
+    /// a.push((1, 'a'));
+    /// b.push((2, 'c'));
+    ///
+    /// a.append(&mut b);
+    /// assert!(b.is_empty());
+    /// assert_eq!(Some((2, 'c')), a.pop());
+    /// assert_eq!(Some((1, 'a')), a.pop());
+    /// assert_eq!(Some((1, 'b')), a.pop());
+    /// ```
+    pub fn append(&mut self, other: &mut Self) {
+        let offset = self.seq;
+        other
+            .heap
+            .data
+            .iter_mut()
+            .for_each(|entry| entry.seq += offset);
+
+        self.seq += other.seq;
+        other.seq = 0;
+        self.heap.append(&mut other.heap);
+    }
+
+    /// Retains only the elements for which the predicate returns `true`.
+    /// The heap is rebuilt if any element was removed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::stable_binary_heap::StableBinaryHeap;
+    ///
+    /// let mut heap = StableBinaryHeap::from(vec![1, 2, 3, 4, 5, 6]);
+    /// heap.retain(|x| x % 2 == 0);
+    ///
+    /// assert_eq!(vec![2, 4, 6], heap.into_sorted_vec());
+    /// ```
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.heap.retain(|entry| f(&entry.item));
+    }
+
+    /// Consumes the stable binary heap and returns a vector with the
+    /// elements in ascending priority by the comparator, the top of the
+    /// heap last, so a min-heap returns the values in descending order.
+    /// The equal elements are in the reverse order they were pushed, so
+    /// popping from the end of the vector follows the order of the heap.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::stable_binary_heap::StableBinaryHeap;
+    ///
+    /// let heap = StableBinaryHeap::from(vec![4, 1, 5, 3, 2]);
+    /// assert_eq!(vec![1, 2, 3, 4, 5], heap.into_sorted_vec());
+    ///
+    /// let mut heap = StableBinaryHeap::new_min();
+    /// heap.extend(vec![4, 1, 5, 3, 2]);
+    /// assert_eq!(vec![5, 4, 3, 2, 1], heap.into_sorted_vec());
+    /// ```
+    pub fn into_sorted_vec(self) -> Vec<T> {
+        self.heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|entry| entry.item)
+            .collect()
+    }
+
+    /// Consumes the stable binary heap and returns an iterator which
+    /// yields the elements in heap order, the greatest first.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::stable_binary_heap::StableBinaryHeap;
+    ///
+    /// let heap = StableBinaryHeap::from(vec![4, 1, 5, 3, 2]);
+    /// let xs: Vec<_> = heap.into_iter_sorted().take(2).collect();
+    ///
+    /// assert_eq!(vec![5, 4], xs);
+    /// ```
+    pub fn into_iter_sorted(self) -> IntoIterSorted<T, C> {
+        IntoIterSorted::new(self)
+    }
+
+    /// Checks that every element of the stable binary heap comes after its
+    /// parent, by the comparator and then by the insertion sequence. The
+    /// error names the index of the first element which is out of order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::stable_binary_heap::StableBinaryHeap;
+    ///
+    /// let heap = StableBinaryHeap::from(vec![1, 5, 2, 4, 3]);
+    /// assert!(heap.validate().is_ok());
+    /// ```
+    pub fn validate(&self) -> Result<(), InvariantError> {
+        self.heap.validate()
+    }
+
+    /// Returns the sequence number for a new element.
+    fn next_seq(&mut self) -> u64 {
+        let seq = self.seq;
+        self.seq += 1;
+        seq
+    }
+}
+
+impl<T, C> StableBinaryHeap<T, C> {
+    /// Returns an iterator that is visiting all values in the stable binary heap.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter::new(self.heap.data.iter())
+    }
+
+    /// Returns the greatest element in the stable binary heap,
+    /// or `None` if it is empty.
+    pub fn peek(&self) -> Option<&T> {
+        self.heap.peek().map(|entry| &entry.item)
+    }
+
+    /// Removes all the elements from the stable binary heap and returns them
+    /// in an arbitrary order. The allocated memory is kept.
+    pub fn drain(&mut self) -> Drain<'_, T> {
+        self.seq = 0;
+        Drain::new(self.heap.drain())
+    }
+
+    /// Removes all the elements from the stable binary heap.
+    pub fn clear(&mut self) {
+        self.seq = 0;
+        self.heap.clear();
+    }
+
+    /// Returns the number of elements the stable binary heap can hold.
+    pub fn capacity(&self) -> usize {
+        self.heap.capacity()
+    }
+
+    /// Reserves the minimum capacity for additional more elements
+    /// to be inserted in the stable binary heap.
+    pub fn reserve_exact(&mut self, additional: usize) {
+        self.heap.reserve_exact(additional);
+    }
+
+    /// Reserves capacity for at least additional more elements
+    /// to be inserted in the stable binary heap.
+    pub fn reserve(&mut self, additional: usize) {
+        self.heap.reserve(additional);
+    }
+
+    /// Shrinks the allocated memory.
+    pub fn shrink_to_fit(&mut self) {
+        self.heap.shrink_to_fit();
+    }
+
+    /// Returns the number of elements in the stable binary heap.
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// Returns a flag indicating whenever the stable binary heap is empty.
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::lcg;
+
+    #[test]
+    fn new_pass() {
+        let h = StableBinaryHeap::<u8>::new();
+        assert!(h.is_empty());
+        assert_eq!(None, h.peek());
+    }
+
+    #[test]
+    fn debug_pass() {
+        let h = StableBinaryHeap::from(vec![1]);
+        assert_eq!("[1]", format!("{:?}", h));
+    }
+
+    #[test]
+    fn fifo_pass() {
+        let mut seed = 5u64;
+        let mut h = StableBinaryHeap::with_key(|x: &(u64, usize)| x.0);
+        let mut xs = Vec::new();
+        for i in 0..1000 {
+            let x = (lcg(&mut seed) % 10, i);
+            xs.push(x);
+            h.push(x);
+        }
+        assert_eq!(Ok(()), h.validate());
+
+        // A stable sort by descending priority keeps the insertion order.
+        xs.sort_by_key(|x| std::cmp::Reverse(x.0));
+        let ys: Vec<_> = std::iter::from_fn(|| h.pop()).collect();
+        assert_eq!(xs, ys);
+    }
+
+    #[test]
+    fn fifo_interleaved_pass() {
+        let mut seed = 9u64;
+        let mut h = StableBinaryHeap::with_key(|x: &(u64, usize)| x.0);
+        let mut model: Vec<(u64, usize)> = Vec::new();
+
+        for i in 0..2000 {
+            if lcg(&mut seed) % 3 == 1 {
+                // The first pushed of the greatest elements.
+                let top = model
+                    .iter()
+                    .enumerate()
+                    .max_by(|(i, a), (j, b)| a.0.cmp(&b.0).then(j.cmp(i)))
+                    .map(|(i, _)| i);
+                assert_eq!(top.map(|i| model.remove(i)), h.pop());
+            } else {
+                let x = (lcg(&mut seed) % 5, i);
+                model.push(x);
+                h.push(x);
+            }
+        }
+    }
+
+    #[test]
+    fn min_fifo_pass() {
+        let mut h = StableBinaryHeap::from_comparator(MinComparator);
+        h.push(2);
+        h.push(1);
+        assert_eq!(Some(1), h.pop());
+
+        let mut h =
+            StableBinaryHeap::with_comparator(|a: &(u8, char), b: &(u8, char)| b.0.cmp(&a.0));
+        h.extend(vec![(2, 'a'), (1, 'b'), (2, 'c'), (1, 'd')]);
+        let xs: Vec<_> = h.into_iter_sorted().collect();
+        assert_eq!(vec![(1, 'b'), (1, 'd'), (2, 'a'), (2, 'c')], xs);
+    }
+
+    #[test]
+    fn append_pass() {
+        let key = |x: &(u8, char)| x.0;
+        let mut a = StableBinaryHeap::from_comparator(KeyComparator(key));
+        let mut b = StableBinaryHeap::from_comparator(KeyComparator(key));
+        a.push((1, 'a'));
+        b.push((1, 'c'));
+        b.push((1, 'd'));
+        a.push((1, 'b'));
+        b.push((3, 'e'));
+
+        a.append(&mut b);
+        assert!(b.is_empty());
+        assert_eq!(Ok(()), a.validate());
+
+        a.push((1, 'f'));
+        let xs: String = std::iter::from_fn(|| a.pop()).map(|x| x.1).collect();
+        assert_eq!("eabcdf", xs);
+    }
+
+    #[test]
+    fn retain_pass() {
+        let mut h = StableBinaryHeap::with_key(|x: &(u8, char)| x.0);
+        h.extend(vec![(1, 'a'), (2, 'b'), (1, 'c'), (2, 'd'), (1, 'e')]);
+        h.retain(|x| x.1 != 'b');
+
+        let xs: String = h.into_iter_sorted().map(|x| x.1).collect();
+        assert_eq!("dace", xs);
+    }
+
+    #[test]
+    fn into_sorted_vec_pass() {
+        let mut h = StableBinaryHeap::with_key(|x: &(u8, char)| x.0);
+        h.extend(vec![(1, 'a'), (2, 'b'), (1, 'c')]);
+
+        let xs: String = h.into_sorted_vec().into_iter().map(|x| x.1).collect();
+        assert_eq!("cab", xs);
+    }
+
+    #[test]
+    fn clear_pass() {
+        let mut h = StableBinaryHeap::from(vec![3, 1, 2]);
+        assert_eq!(3, h.drain().count());
+        assert!(h.is_empty());
+
+        h.push(1);
+        h.clear();
+        assert!(h.is_empty());
+        assert_eq!(0, h.seq);
+    }
+
+    #[test]
+    fn validate_fail() {
+        let mut h = StableBinaryHeap::from(vec![1, 1]);
+        h.heap.data.swap(0, 1);
+
+        assert_eq!(
+            Err(InvariantError::Order {
+                parent: 0,
+                child: 1
+            }),
+            h.validate()
+        );
+    }
+}
@@ -0,0 +1,9 @@
+//! Binary heap with stable, first in first out, order of equal elements
+mod convert;
+mod iter;
+mod main;
+mod peek_mut;
+
+pub use crate::stable_binary_heap::iter::*;
+pub use crate::stable_binary_heap::main::StableBinaryHeap;
+pub use crate::stable_binary_heap::peek_mut::PeekMut;
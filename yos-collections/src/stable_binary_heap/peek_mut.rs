@@ -0,0 +1,101 @@
+//! A guard which gives mutable access to the greatest element of a stable binary heap.
+use super::main::{Entry, Stable};
+use crate::binary_heap;
+use crate::compare::Compare;
+
+use core::fmt::Debug;
+use core::ops::{Deref, DerefMut};
+
+/// A guard over the greatest element of a `StableBinaryHeap`, returned by
+/// `StableBinaryHeap::peek_mut`. When the guard is dropped the element is
+/// sifted down, if it was changed, so the heap is valid again. The element
+/// keeps its insertion sequence.
+pub struct PeekMut<'a, T: 'a, C: 'a + Compare<T>> {
+    inner: binary_heap::PeekMut<'a, Entry<T>, Stable<C>>,
+}
+
+impl<'a, T, C: Compare<T>> PeekMut<'a, T, C> {
+    /// Creates a new guard over the guard of the inner heap.
+    pub(crate) fn new(inner: binary_heap::PeekMut<'a, Entry<T>, Stable<C>>) -> Self {
+        Self { inner }
+    }
+
+    /// Removes the greatest element from the stable binary heap and returns it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::stable_binary_heap::{PeekMut, StableBinaryHeap};
+    ///
+    /// let mut heap = StableBinaryHeap::from(vec![1, 3, 2]);
+    /// let top = heap.peek_mut().unwrap();
+    ///
+    /// assert_eq!(3, PeekMut::pop(top));
+    /// assert_eq!(Some(&2), heap.peek());
+    /// ```
+    pub fn pop(this: PeekMut<'a, T, C>) -> T {
+        binary_heap::PeekMut::pop(this.inner).item
+    }
+}
+
+impl<T, C: Compare<T>> Deref for PeekMut<'_, T, C> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.inner.item
+    }
+}
+
+impl<T, C: Compare<T>> DerefMut for PeekMut<'_, T, C> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.inner.item
+    }
+}
+
+impl<T: Debug, C: Compare<T>> Debug for PeekMut<'_, T, C> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("PeekMut").field(&self.inner.item).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::stable_binary_heap::{PeekMut, StableBinaryHeap};
+
+    #[test]
+    fn peek_mut_pass() {
+        let mut h = StableBinaryHeap::from(vec![5, 1, 4, 2, 3]);
+        {
+            let mut top = h.peek_mut().unwrap();
+            assert_eq!(5, *top);
+            *top = 0;
+        }
+        assert_eq!(Ok(()), h.validate());
+        assert_eq!(Some(&4), h.peek());
+        assert_eq!(vec![0, 1, 2, 3, 4], h.into_sorted_vec());
+    }
+
+    #[test]
+    fn peek_mut_keeps_seq_pass() {
+        let mut h = StableBinaryHeap::with_key(|x: &(u8, char)| x.0);
+        h.extend(vec![(3, 'a'), (1, 'b'), (1, 'c')]);
+        {
+            let mut top = h.peek_mut().unwrap();
+            top.0 = 1;
+        }
+
+        // The changed element was pushed first, so it stays first.
+        let xs: String = h.into_iter_sorted().map(|x| x.1).collect();
+        assert_eq!("abc", xs);
+    }
+
+    #[test]
+    fn peek_mut_pop_pass() {
+        let mut h = StableBinaryHeap::new_min();
+        h.extend(vec![3, 1, 2]);
+
+        let top = h.peek_mut().unwrap();
+        assert_eq!(1, PeekMut::pop(top));
+        assert_eq!(vec![3, 2], h.into_sorted_vec());
+    }
+}
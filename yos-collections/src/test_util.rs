@@ -0,0 +1,9 @@
+//! Helpers shared by the test modules of the crate.
+
+/// A tiny linear congruential generator, good enough to shuffle test data.
+pub(crate) fn lcg(seed: &mut u64) -> u64 {
+    *seed = seed
+        .wrapping_mul(6364136223846793005)
+        .wrapping_add(1442695040888963407);
+    *seed >> 33
+}
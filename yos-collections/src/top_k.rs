@@ -0,0 +1,417 @@
+//! Bounded selection of the greatest elements of a stream.
+use crate::binary_heap::{BinaryHeap, IntoIter, Iter};
+use crate::compare::{
+    Compare, FnComparator, KeyComparator, MaxComparator, MinComparator, ReverseComparator,
+};
+use crate::invariant::InvariantError;
+
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::fmt::Debug;
+use core::mem::replace;
+
+/// Keeps the `k` greatest elements seen so far, according to the
+/// comparator `C`, in O(k) memory.
+///
+/// The elements are kept in a binary heap with the reverse order, so the
+/// least of the kept elements is at the top. When the collection is full
+/// a greater element replaces the top in place and is sifted down, there
+/// is no push followed by a pop.
+pub struct TopK<T, C = MaxComparator> {
+    heap: BinaryHeap<T, ReverseComparator<C>>,
+    k: usize,
+}
+
+impl<T: Clone, C: Clone> Clone for TopK<T, C> {
+    fn clone(&self) -> Self {
+        Self {
+            heap: self.heap.clone(),
+            k: self.k,
+        }
+    }
+}
+
+impl<T: Debug, C> Debug for TopK<T, C> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: Ord> TopK<T> {
+    /// Creates a new instance which keeps the `k` greatest elements.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::top_k::TopK;
+    ///
+    /// let mut top = TopK::new(2);
+    /// top.extend(vec![3, 1, 4, 1, 5]);
+    ///
+    /// assert_eq!(vec![4, 5], top.into_sorted_vec());
+    /// ```
+    pub fn new(k: usize) -> Self {
+        Self::from_comparator(k, MaxComparator)
+    }
+}
+
+impl<T: Ord> TopK<T, MinComparator> {
+    /// Creates a new instance which keeps the `k` smallest elements.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::top_k::TopK;
+    ///
+    /// let mut top = TopK::new_min(2);
+    /// top.extend(vec![3, 1, 4, 1, 5]);
+    ///
+    /// assert_eq!(vec![1, 1], top.into_sorted_vec());
+    /// ```
+    pub fn new_min(k: usize) -> Self {
+        Self::from_comparator(k, MinComparator)
+    }
+}
+
+impl<T, F> TopK<T, FnComparator<F>>
+where
+    F: Fn(&T, &T) -> Ordering,
+{
+    /// Creates a new instance which keeps the `k` greatest elements,
+    /// according to a closure.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::top_k::TopK;
+    ///
+    /// let mut top = TopK::with_comparator(2, |a: &i32, b: &i32| a.abs().cmp(&b.abs()));
+    /// top.extend(vec![-7, 1, 5, -2]);
+    ///
+    /// assert_eq!(vec![5, -7], top.into_sorted_vec());
+    /// ```
+    pub fn with_comparator(k: usize, f: F) -> Self {
+        Self::from_comparator(k, FnComparator(f))
+    }
+}
+
+impl<T, K, F> TopK<T, KeyComparator<F>>
+where
+    K: Ord,
+    F: Fn(&T) -> K,
+{
+    /// Creates a new instance which keeps the `k` elements with the
+    /// greatest keys, extracted with a closure.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::top_k::TopK;
+    ///
+    /// let mut top = TopK::with_key(1, |x: &(&str, u32)| x.1);
+    /// top.extend(vec![("a", 3), ("b", 9), ("c", 5)]);
+    ///
+    /// assert_eq!(vec![("b", 9)], top.into_sorted_vec());
+    /// ```
+    pub fn with_key(k: usize, f: F) -> Self {
+        Self::from_comparator(k, KeyComparator(f))
+    }
+}
+
+impl<T, C: Compare<T>> TopK<T, C> {
+    /// Creates a new instance which keeps the `k` greatest elements,
+    /// according to the given comparator.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::compare::MinComparator;
+    /// use yos_collections::top_k::TopK;
+    ///
+    /// let mut top = TopK::from_comparator(1, MinComparator);
+    /// top.push(2);
+    /// top.push(1);
+    ///
+    /// assert_eq!(Some(&1), top.peek());
+    /// ```
+    pub fn from_comparator(k: usize, cmp: C) -> Self {
+        Self {
+            heap: BinaryHeap::from_comparator(ReverseComparator(cmp)),
+            k,
+        }
+    }
+
+    /// Offers a value to the collection. Returns the element which is
+    /// left out: `None` while there are less than `k` elements, otherwise
+    /// the evicted least element or the value itself when it is not
+    /// greater than all the kept elements.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::top_k::TopK;
+    ///
+    /// let mut top = TopK::new(2);
+    /// assert_eq!(None, top.push(5));
+    /// assert_eq!(None, top.push(3));
+    /// assert_eq!(Some(3), top.push(8));
+    /// assert_eq!(Some(1), top.push(1));
+    /// ```
+    pub fn push(&mut self, value: T) -> Option<T> {
+        if self.heap.len() < self.k {
+            self.heap.push(value);
+            return None;
+        }
+
+        let inner = &self.heap.cmp.0;
+        match self.heap.data.first_mut() {
+            Some(least) if inner.compare(&value, least).is_gt() => {
+                let old = replace(least, value);
+                self.heap.bubble_down(0);
+                Some(old)
+            }
+            _ => Some(value),
+        }
+    }
+
+    /// Merges the elements kept by another collection into this one,
+    /// which keeps its own `k`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::top_k::TopK;
+    ///
+    /// let mut a = TopK::new(3);
+    /// a.extend(vec![1, 8, 4, 6]);
+    /// let mut b = TopK::new(3);
+    /// b.extend(vec![7, 2, 9, 3]);
+    ///
+    /// a.merge(b);
+    /// assert_eq!(vec![7, 8, 9], a.into_sorted_vec());
+    /// ```
+    pub fn merge(&mut self, other: Self) {
+        other.heap.into_iter().for_each(|x| {
+            self.push(x);
+        });
+    }
+
+    /// Consumes the collection and returns a vector with the kept elements
+    /// in ascending order by the comparator, the best element last. With
+    /// `new_min` the values come in descending order, the smallest last.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use yos_collections::top_k::TopK;
+    ///
+    /// let mut top = TopK::new(3);
+    /// top.extend(vec![2, 9, 4, 7, 1]);
+    /// assert_eq!(vec![4, 7, 9], top.into_sorted_vec());
+    ///
+    /// let mut top = TopK::new_min(3);
+    /// top.extend(vec![2, 9, 4, 7, 1]);
+    /// assert_eq!(vec![4, 2, 1], top.into_sorted_vec());
+    /// ```
+    pub fn into_sorted_vec(self) -> Vec<T> {
+        // The heap sorts by the reverse order, the greatest element first.
+        let mut xs = self.heap.into_sorted_vec();
+        xs.reverse();
+        xs
+    }
+
+    /// Checks that the least kept element is at the top of the inner heap
+    /// and that there are no more than `k` elements.
+    pub fn validate(&self) -> Result<(), InvariantError> {
+        if self.heap.len() > self.k {
+            return Err(InvariantError::Length {
+                expected: self.k,
+                found: self.heap.len(),
+            });
+        }
+
+        self.heap.validate()
+    }
+}
+
+impl<T, C> TopK<T, C> {
+    /// Returns the least of the kept elements, the first one to be evicted,
+    /// or `None` if the collection is empty.
+    pub fn peek(&self) -> Option<&T> {
+        self.heap.peek()
+    }
+
+    /// Returns an iterator that is visiting the kept elements
+    /// in an arbitrary order.
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.heap.iter()
+    }
+
+    /// Returns the maximum number of kept elements.
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    /// Removes all the elements.
+    pub fn clear(&mut self) {
+        self.heap.clear();
+    }
+
+    /// Returns the number of kept elements.
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// Returns a flag indicating whenever no element is kept.
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+}
+
+impl<T, C: Compare<T>> Extend<T> for TopK<T, C> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|x| {
+            self.push(x);
+        });
+    }
+}
+
+impl<T, C> IntoIterator for TopK<T, C> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        self.heap.into_iter()
+    }
+}
+
+impl<'a, T, C> IntoIterator for &'a TopK<T, C> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+/// Returns the `k` greatest elements of an iterator in ascending order,
+/// the greatest element last, using O(k) memory.
+///
+/// # Example
+///
+/// ```
+/// use yos_collections::top_k::top_k;
+///
+/// assert_eq!(vec![98, 99], top_k(0..100, 2));
+/// ```
+pub fn top_k<T: Ord, I: IntoIterator<Item = T>>(iter: I, k: usize) -> Vec<T> {
+    let mut top = TopK::new(k);
+    top.extend(iter);
+    top.into_sorted_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::lcg;
+
+    #[test]
+    fn push_pass() {
+        let mut seed = 7u64;
+        let xs: Vec<u64> = (0..2000).map(|_| lcg(&mut seed) % 500).collect();
+
+        let mut top = TopK::new(25);
+        for x in xs.iter() {
+            top.push(*x);
+            assert_eq!(Ok(()), top.validate());
+        }
+        assert_eq!(25, top.len());
+
+        let mut ys = xs;
+        ys.sort_unstable();
+        assert_eq!(ys[ys.len() - 25..].to_vec(), top.into_sorted_vec());
+    }
+
+    #[test]
+    fn push_evict_pass() {
+        let mut top = TopK::new(2);
+        assert_eq!(None, top.push(4));
+        assert_eq!(None, top.push(6));
+        assert_eq!(Some(&4), top.peek());
+
+        assert_eq!(Some(4), top.push(5));
+        assert_eq!(Some(3), top.push(3));
+        assert_eq!(Some(5), top.push(5));
+        assert_eq!(Some(&5), top.peek());
+    }
+
+    #[test]
+    fn zero_pass() {
+        let mut top = TopK::new(0);
+        assert_eq!(Some(1), top.push(1));
+        assert!(top.is_empty());
+        assert_eq!(None, top.peek());
+    }
+
+    #[test]
+    fn min_pass() {
+        let mut seed = 3u64;
+        let xs: Vec<u64> = (0..1000).map(|_| lcg(&mut seed)).collect();
+
+        let mut top = TopK::new_min(10);
+        top.extend(xs.iter().cloned());
+        assert_eq!(Ok(()), top.validate());
+
+        let mut ys = xs;
+        ys.sort_unstable();
+        ys.truncate(10);
+        ys.reverse();
+        assert_eq!(ys, top.into_sorted_vec());
+    }
+
+    #[test]
+    fn merge_pass() {
+        let mut seed = 11u64;
+        let xs: Vec<u64> = (0..600).map(|_| lcg(&mut seed) % 1000).collect();
+
+        let mut a = TopK::new(20);
+        a.extend(xs[..300].iter().cloned());
+        let mut b = TopK::new(20);
+        b.extend(xs[300..].iter().cloned());
+
+        a.merge(b);
+        assert_eq!(Ok(()), a.validate());
+        assert_eq!(top_k(xs, 20), a.into_sorted_vec());
+    }
+
+    #[test]
+    fn top_k_pass() {
+        assert_eq!(vec![7, 8, 9], top_k(vec![9, 1, 8, 2, 7, 3], 3));
+        assert_eq!(vec![1, 2], top_k(vec![2, 1], 5));
+        assert!(top_k(Vec::<u8>::new(), 5).is_empty());
+    }
+
+    #[test]
+    fn validate_fail() {
+        let mut top = TopK::new(3);
+        top.extend(vec![1, 2, 3]);
+        top.k = 2;
+
+        assert_eq!(
+            Err(InvariantError::Length {
+                expected: 2,
+                found: 3
+            }),
+            top.validate()
+        );
+    }
+
+    #[test]
+    fn debug_iter_pass() {
+        let mut top = TopK::new(1);
+        top.push(2);
+        assert_eq!("[2]", format!("{:?}", top));
+        assert_eq!(vec![&2], (&top).into_iter().collect::<Vec<_>>());
+        assert_eq!(vec![2], top.into_iter().collect::<Vec<_>>());
+    }
+}
@@ -213,7 +213,7 @@ mod tests {
         let d = square_euclidean(&xs, &ys);
 
         let e: f32 = 25.;
-        assert!((e - d).abs() < std::f32::EPSILON);
+        assert!((e - d).abs() < f32::EPSILON);
     }
 
     #[test]
@@ -222,7 +222,7 @@ mod tests {
         let ys = [3., 4.];
         let d = manhatann(&xs, &ys);
         let e: f32 = 7.;
-        assert!((e - d).abs() < std::f32::EPSILON);
+        assert!((e - d).abs() < f32::EPSILON);
     }
 
     #[test]
@@ -231,7 +231,7 @@ mod tests {
         let ys = [3., 4.];
         let d = chebyshev(&xs, &ys);
         let e: f32 = 4.;
-        assert!((e - d).abs() < std::f32::EPSILON);
+        assert!((e - d).abs() < f32::EPSILON);
     }
 
     #[test]
@@ -266,34 +266,34 @@ mod tests {
     fn sift3_pass() {
         let d = sift3("hannah".as_bytes(), "hanna".as_bytes(), 5);
         let e: f32 = 0.5;
-        assert!((e - d).abs() < std::f32::EPSILON);
+        assert!((e - d).abs() < f32::EPSILON);
     }
 
     #[test]
     fn sift3_empty_empty() {
         let d = sift3("".as_bytes(), "".as_bytes(), 5);
         let e: f32 = 0.0;
-        assert!((e - d).abs() < std::f32::EPSILON);
+        assert!((e - d).abs() < f32::EPSILON);
     }
 
     #[test]
     fn sift3_str_empty() {
         let d = sift3("hannah".as_bytes(), "".as_bytes(), 5);
-        let e: f32 = "hannah".as_bytes().len() as f32;
-        assert!((e - d).abs() < std::f32::EPSILON);
+        let e: f32 = "hannah".len() as f32;
+        assert!((e - d).abs() < f32::EPSILON);
     }
 
     #[test]
     fn sift3_empty_str() {
         let d = sift3("".as_bytes(), "hanna".as_bytes(), 5);
-        let e: f32 = "hanna".as_bytes().len() as f32;
-        assert!((e - d).abs() < std::f32::EPSILON);
+        let e: f32 = "hanna".len() as f32;
+        assert!((e - d).abs() < f32::EPSILON);
     }
 
     #[test]
     fn sift3_offset() {
         let d = sift3("hannah".as_bytes(), "ahanna".as_bytes(), 5);
         let e: f32 = 3.;
-        assert!((e - d).abs() < std::f32::EPSILON);
+        assert!((e - d).abs() < f32::EPSILON);
     }
 }
@@ -2,6 +2,7 @@
 //! a slice of memory and is able to move the values
 //! from one position to another using diret memory access.
 
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 #![deny(missing_docs)]
 #![deny(unreachable_pub)]
 
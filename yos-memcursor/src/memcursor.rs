@@ -1,5 +1,5 @@
+use core::mem::ManuallyDrop;
 use core::ptr;
-use std::mem::ManuallyDrop;
 
 /// MemCursor represents a cursor in a slice i.e., an index without valid value
 /// (because it was moved from or duplicated).
@@ -19,9 +19,7 @@ impl Default for K2Hasher {
 
 impl Clone for K2Hasher {
     fn clone(&self) -> Self {
-        Self {
-            hashers: [self.hashers[0], self.hashers[1]],
-        }
+        *self
     }
 }
 